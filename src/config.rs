@@ -1,21 +1,66 @@
 use std::env;
 use std::path::PathBuf;
+use std::time::Duration;
 use crate::Path;
+use crate::state::KeyBindings;
+use crate::ui::{DeviceProfile, Theme};
+
+/// Governs how `run`'s main loop paces `update_state`/`Sprite::update` calls.
+/// `Fixed50`/`Fixed60` accumulate wall-clock time and run a fixed-timestep
+/// catch-up loop regardless of how fast a frame draws; `VSync` steps once per
+/// drawn frame and lets the display's swap interval set the pace (the canvas
+/// is built with `present_vsync()` when this is selected).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimingMode {
+    Fixed50,
+    Fixed60,
+    VSync,
+}
+
+impl TimingMode {
+    /// The fixed-timestep duration to accumulate against, or `None` for
+    /// `VSync` (no catch-up loop; one update per drawn frame).
+    pub fn timestep(self) -> Option<Duration> {
+        match self {
+            TimingMode::Fixed50 => Some(Duration::from_nanos(1_000_000_000 / 50)),
+            TimingMode::Fixed60 => Some(Duration::from_nanos(1_000_000_000 / 60)),
+            TimingMode::VSync => None,
+        }
+    }
+}
 
 pub struct Config {
     pub window_title: &'static str,
     pub window_width: u32,
     pub window_height: u32,
-    pub logical_window_width: u32,
-    pub logical_window_height: u32,
-    pub font_path: PathBuf,
-    pub command_font_path: PathBuf,
-    pub emoji_font_path: PathBuf,
-    pub font_size_large: u32,
-    pub font_size_medium: u32,
-    pub font_size_small: u32,
     pub decks_directory: PathBuf,
     pub sfx_directory: PathBuf,
+    /// Where `SoundManager` looks for background tracks: the jukebox's
+    /// free-choice playlist and the fixed per-scene `BgmTrack`s, a sibling
+    /// of `sfx_directory`.
+    pub music_directory: PathBuf,
+    pub themes_directory: PathBuf,
+    /// PNG sprite sheet for the mother sprite's `Idle`/`Correct`/`Incorrect`/
+    /// `SessionComplete` animations, a sibling of `themes_directory`.
+    pub sprite_sheet_path: PathBuf,
+    pub key_bindings: KeyBindings,
+    /// Where `key_bindings` was loaded from. `main()` reloads it from here
+    /// once a controller is opened and its `ControllerType` known, since
+    /// `Config::new` runs before any controller is.
+    pub keybindings_path: PathBuf,
+    /// Where `Settings` is loaded from at startup and saved back to on every
+    /// change made in the options scene, a sibling of `keybindings.toml`.
+    pub settings_path: PathBuf,
+    /// The active screen/device's font tiers and layout margins, chosen by
+    /// `DeviceProfile::load` from `window_width` (optionally overridden by
+    /// `profiles.toml`).
+    pub profile: DeviceProfile,
+    /// The active color palette, loaded from `themes_directory` by name
+    /// (falls back to `Theme::default_theme()` if missing/invalid).
+    pub theme: Theme,
+    /// How the main loop paces updates/draws. Fixed at `Fixed60` for now;
+    /// not yet user-configurable.
+    pub timing_mode: TimingMode,
 }
 
 impl Config {
@@ -47,20 +92,36 @@ impl Config {
         println!("{:?}", base_decks);
         println!("{:?}", sfx_directory);
 
+        // A `keybindings.toml` next to the executable lets a user on a
+        // differently-wired Brick variant override the default SDL→BrickButton
+        // mapping (e.g. swapped volume buttons) without recompiling.
+        let keybindings_path = exe_dir.join("keybindings.toml");
+        let key_bindings = KeyBindings::load(&keybindings_path);
+        let settings_path = exe_dir.join("settings.toml");
+
+        let window_width = 1024;
+        let profile = DeviceProfile::load(base_assets, &exe_dir.join("profiles.toml"), window_width);
+
+        let themes_directory = base_assets.join("themes");
+        let theme = Theme::load(&themes_directory, "default");
+        let sprite_sheet_path = base_assets.join("sprite.png");
+        let music_directory = base_assets.join("music");
+
         Self {
             window_title: "CardBrick v0.1",
-            window_width: 1024,
+            window_width,
             window_height: 768,
-            logical_window_width: 512,
-            logical_window_height: 384,
-            font_path: base_assets.join("font/M1MnRegular-M2Gn.ttf"),
-            command_font_path: base_assets.join("font/Ac437_Tandy1K-II_200L.ttf"),
-            emoji_font_path: base_assets.join("font/M1MnRegular-M2Gn.ttf"),
-            font_size_large: 32,
-            font_size_medium: 24,
-            font_size_small: 10,
             decks_directory: base_decks.to_path_buf(),
             sfx_directory: sfx_directory.to_path_buf(),
+            music_directory,
+            themes_directory,
+            sprite_sheet_path,
+            key_bindings,
+            keybindings_path,
+            settings_path,
+            profile,
+            theme,
+            timing_mode: TimingMode::Fixed60,
         }
     }
 }
\ No newline at end of file
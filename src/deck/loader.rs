@@ -6,21 +6,142 @@ use std::io::{Read, Write};
 use std::path::Path;
 use std::collections::HashMap;
 use std::sync::mpsc::Sender;
+use std::time::UNIX_EPOCH;
 
 // We need to bring our structs into scope from the parent module (deck/mod.rs)
+use super::media;
 use super::{Card, Deck, Note};
+use crate::storage::DatabaseManager;
 use crate::LoaderMessage; // Import the message enum from main.rs
 
-/// The main function for this module. It takes a path to an .apkg file and a
-/// channel sender to report progress.
-pub fn load_apkg(path: &Path, tx: Sender<LoaderMessage>) {
+/// Deck name and card counts read straight from the .apkg's SQLite
+/// collection, without extracting media or caching notes into the database.
+/// Used by `load_decks_from_directory` to populate `DeckMetadata` for the
+/// selection list before a deck is actually opened for studying.
+pub struct DeckSummary {
+    pub name: String,
+    pub total_count: usize,
+    pub new_count: usize,
+    pub due_count: usize,
+}
+
+/// Opens `path` as a zip archive, extracts its `collection.anki21`/
+/// `collection.anki2` SQLite database to a temp file (same extraction as
+/// `load_apkg`, minus media), and reads the deck name out of `col.decks`
+/// plus new/due counts out of `cards`. `today` should be
+/// `scheduler::current_day_number()`; a card counts as new when
+/// `ivl == 0 && lapses == 0` and due when `ivl > 0 && due <= today`,
+/// matching `scheduler::build_due_queue`'s own classification.
+pub fn read_deck_summary(path: &Path, today: i64) -> Result<DeckSummary, String> {
+    (|| -> Result<DeckSummary, Box<dyn std::error::Error>> {
+        let file = fs::File::open(path)?;
+        let mut archive = zip::ZipArchive::new(file)?;
+
+        let db_filename = if archive.file_names().any(|name| name == "collection.anki21") {
+            "collection.anki21"
+        } else {
+            "collection.anki2"
+        };
+
+        let mut db_file = archive.by_name(db_filename)?;
+        let mut db_data = Vec::new();
+        db_file.read_to_end(&mut db_data)?;
+
+        let mut temp_file = tempfile::NamedTempFile::new()?;
+        temp_file.write_all(&db_data)?;
+        let temp_path = temp_file.into_temp_path();
+
+        let conn = rusqlite::Connection::open(&temp_path)?;
+
+        let decks_json: String = conn.query_row("SELECT decks FROM col", [], |row| row.get(0))?;
+        let name = first_deck_name(&decks_json).unwrap_or_else(|| {
+            path.file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("unknown_deck")
+                .to_string()
+        });
+
+        let mut stmt = conn.prepare("SELECT ivl, lapses, due FROM cards")?;
+        let rows = stmt.query_map([], |row| {
+            let ivl: i64 = row.get(0)?;
+            let lapses: i64 = row.get(1)?;
+            let due: i64 = row.get(2)?;
+            Ok((ivl, lapses, due))
+        })?;
+
+        let mut total_count = 0usize;
+        let mut new_count = 0usize;
+        let mut due_count = 0usize;
+        for row in rows {
+            let (ivl, lapses, due) = row?;
+            total_count += 1;
+            if ivl == 0 && lapses == 0 {
+                new_count += 1;
+            } else if ivl > 0 && due <= today {
+                due_count += 1;
+            }
+        }
+
+        Ok(DeckSummary { name, total_count, new_count, due_count })
+    })()
+    .map_err(|e| e.to_string())
+}
+
+/// Pulls a deck's `name` out of the `col.decks` JSON blob (keyed by deck id).
+/// Anki's default collection always has a "Default" deck alongside any real
+/// ones, so the first non-"Default" name is preferred; falls back to
+/// whatever name is present, or `None` if the JSON has no deck entries at all.
+fn first_deck_name(decks_json: &str) -> Option<String> {
+    let parsed: serde_json::Value = serde_json::from_str(decks_json).ok()?;
+    let decks = parsed.as_object()?;
+    let names = || decks.values().filter_map(|deck| deck.get("name").and_then(|n| n.as_str()));
+    names()
+        .find(|name| *name != "Default")
+        .or_else(|| names().next())
+        .map(String::from)
+}
+
+/// The main function for this module. It takes a path to an .apkg file, the
+/// deck's id (used to namespace its extracted media cache), and a channel
+/// sender to report progress.
+pub fn load_apkg(path: &Path, deck_id: &str, tx: Sender<LoaderMessage>) {
     // This function now sends its result through the channel instead of returning it.
     let result = (|| -> Result<Deck, Box<dyn std::error::Error>> {
         println!("Attempting to load deck from: {:?}", path);
 
+        let mtime = fs::metadata(path)?.modified()?.duration_since(UNIX_EPOCH)?.as_secs() as i64;
+        let db = DatabaseManager::new(deck_id)?;
+
+        // `sync_deck` stamps `last_synced_at` once it's finished reconciling a
+        // fresh parse against `card_state`. If the deck file hasn't been
+        // touched since, `card_state` (plus the notes cached below) already
+        // reflects exactly what a fresh parse would produce, so skip the
+        // zip/SQLite work entirely and rebuild the `Deck` from the database.
+        if db.last_synced_at()?.map_or(false, |synced_at| synced_at == mtime) {
+            println!("Deck {} unchanged since last sync; skipping .apkg parse.", deck_id);
+            let notes = db.load_cached_notes()?;
+            let cards = db
+                .load_card_states()?
+                .into_iter()
+                .filter(|(_, state)| !state.hidden)
+                .map(|(id, state)| Card {
+                    id,
+                    note_id: state.note_id,
+                    due: state.due,
+                    interval: state.interval,
+                    ease_factor: state.ease_factor,
+                    lapses: state.lapses,
+                    stability: state.stability,
+                    difficulty: state.difficulty,
+                })
+                .collect();
+            tx.send(LoaderMessage::Progress(1.0)).unwrap();
+            return Ok(Deck { cards, notes });
+        }
+
         let file = fs::File::open(path)?;
         let mut archive = zip::ZipArchive::new(file)?;
-        
+
         let db_filename = if archive.file_names().any(|name| name == "collection.anki21") {
             "collection.anki21"
         } else {
@@ -30,32 +151,43 @@ pub fn load_apkg(path: &Path, tx: Sender<LoaderMessage>) {
         let mut db_file = archive.by_name(db_filename)?;
         let mut db_data = Vec::new();
         db_file.read_to_end(&mut db_data)?;
-        tx.send(LoaderMessage::Progress(0.25)).unwrap(); // 25% - DB extracted
+        tx.send(LoaderMessage::Progress(0.2)).unwrap(); // 20% - DB extracted
+
+        let media_lookup = media::extract_media(&mut archive, deck_id)?;
+        println!("Extracted {} media file(s).", media_lookup.len());
+        tx.send(LoaderMessage::Progress(0.4)).unwrap(); // 40% - media extracted
 
         let mut temp_file = tempfile::NamedTempFile::new()?;
         temp_file.write_all(&db_data)?;
         let temp_path = temp_file.into_temp_path();
-        
+
         let conn = rusqlite::Connection::open(&temp_path)?;
         println!("Successfully opened Anki database.");
-        
+
         // --- Load Notes ---
         let mut stmt = conn.prepare("SELECT id, flds FROM notes")?;
         let notes_iter = stmt.query_map([], |row| {
             let id: i64 = row.get(0)?;
             let fields_str: String = row.get(1)?;
             let fields: Vec<String> = fields_str.split('\x1f').map(String::from).collect();
-            Ok(Note { id, fields })
+            Ok((id, fields))
         })?;
 
         let mut notes_map = HashMap::new();
         for note_result in notes_iter {
-            let note = note_result?;
-            notes_map.insert(note.id, note);
+            let (id, fields) = note_result?;
+            let media = fields
+                .iter()
+                .flat_map(|field| media::scan_field_media(field, &media_lookup))
+                .collect();
+            notes_map.insert(id, Note { id, fields, media });
         }
         println!("Loaded {} notes.", notes_map.len());
+        for note in notes_map.values() {
+            db.cache_note(note)?;
+        }
         tx.send(LoaderMessage::Progress(0.75)).unwrap(); // 75% - Notes loaded
-        
+
         // --- Load Cards ---
         let mut stmt = conn.prepare("SELECT id, nid, due, ivl, factor, lapses FROM cards")?;
         let cards_iter = stmt.query_map([], |row| {
@@ -63,6 +195,7 @@ pub fn load_apkg(path: &Path, tx: Sender<LoaderMessage>) {
                 id: row.get(0)?, note_id: row.get(1)?,
                 due: row.get(2)?, interval: row.get(3)?,
                 ease_factor: row.get(4)?, lapses: row.get(5)?,
+                stability: None, difficulty: None,
             })
         })?;
 
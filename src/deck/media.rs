@@ -0,0 +1,184 @@
+// src/deck/media.rs
+// Extracts the images/audio an .apkg packages alongside its notes, and
+// resolves the `<img src="...">`/`[sound:...]` references inside note
+// fields to the extracted files on disk.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// One media asset a note field points at, resolved to where it was
+/// extracted. Kept separate from images so callers that only care about one
+/// kind (e.g. a renderer drawing pictures) don't have to inspect extensions.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MediaRef {
+    Image(PathBuf),
+    Audio(PathBuf),
+}
+
+/// File extensions treated as audio; everything else `scan_field_media`
+/// resolves through `[sound:...]` is assumed to be an image.
+const AUDIO_EXTENSIONS: &[&str] = &["mp3", "ogg", "flac", "wav", "mod"];
+
+/// Where media extracted from a deck's .apkg is cached, one subdirectory per
+/// deck so two decks whose media happens to share a filename (e.g. both
+/// bundling a generic `1.mp3`) don't clobber each other.
+pub fn media_directory(deck_id: &str) -> PathBuf {
+    Path::new("anki/history/media").join(deck_id)
+}
+
+/// Reads the archive-root `media` file (a JSON object mapping stringified
+/// indices to their original filenames, e.g. `{"0":"dog.jpg"}`), extracts
+/// each entry named by the map into `media_directory(deck_id)`, and returns
+/// a lookup from original filename to its extracted path. A package with no
+/// `media` file (no pictures or sounds) just yields an empty lookup.
+pub fn extract_media(
+    archive: &mut zip::ZipArchive<fs::File>,
+    deck_id: &str,
+) -> Result<HashMap<String, PathBuf>, Box<dyn std::error::Error>> {
+    let manifest: HashMap<String, String> = match archive.by_name("media") {
+        Ok(mut entry) => {
+            let mut contents = String::new();
+            entry.read_to_string(&mut contents)?;
+            serde_json::from_str(&contents)?
+        }
+        Err(zip::result::ZipError::FileNotFound) => return Ok(HashMap::new()),
+        Err(e) => return Err(e.into()),
+    };
+
+    let out_dir = media_directory(deck_id);
+    fs::create_dir_all(&out_dir)?;
+
+    let mut lookup = HashMap::new();
+    for (index, original_name) in manifest {
+        // `original_name` comes straight from the untrusted `media` JSON
+        // inside an imported .apkg; keep only its file-name component so a
+        // manifest entry like `"../../../etc/passwd"` or an absolute path
+        // can't escape `out_dir` (zip-slip via the media manifest rather
+        // than the zip entry names themselves).
+        let Some(safe_name) = sanitized_file_name(&original_name) else { continue };
+
+        // The manifest can mention an entry the archive doesn't actually
+        // contain; skip it rather than failing the whole load.
+        let mut entry = match archive.by_name(&index) {
+            Ok(entry) => entry,
+            Err(_) => continue,
+        };
+        let mut data = Vec::new();
+        entry.read_to_end(&mut data)?;
+        let out_path = out_dir.join(&safe_name);
+        fs::write(&out_path, &data)?;
+        lookup.insert(original_name, out_path);
+    }
+    Ok(lookup)
+}
+
+/// Strips `name` down to its last path component, dropping any leading
+/// directories and parent-dir references, so the result is always safe to
+/// join directly onto `out_dir`. Returns `None` when that component is empty,
+/// `.` or `..`.
+fn sanitized_file_name(name: &str) -> Option<String> {
+    let file_name = Path::new(name).file_name()?.to_str()?;
+    if file_name.is_empty() || file_name == "." || file_name == ".." {
+        return None;
+    }
+    Some(file_name.to_string())
+}
+
+/// Scans a note field for `<img src="...">` and `[sound:...]` references and
+/// resolves each through `lookup`. A reference to a file the package didn't
+/// actually include (missing from `lookup`) is dropped rather than erroring
+/// — a card missing one asset should still be usable for its text.
+pub fn scan_field_media(field: &str, lookup: &HashMap<String, PathBuf>) -> Vec<MediaRef> {
+    let mut refs = Vec::new();
+    for filename in scan_attr_values(field, "<img", "src") {
+        if let Some(path) = lookup.get(filename) {
+            refs.push(MediaRef::Image(path.clone()));
+        }
+    }
+    for filename in scan_sound_refs(field) {
+        if let Some(path) = lookup.get(filename) {
+            if is_audio_filename(filename) {
+                refs.push(MediaRef::Audio(path.clone()));
+            } else {
+                refs.push(MediaRef::Image(path.clone()));
+            }
+        }
+    }
+    refs
+}
+
+fn is_audio_filename(filename: &str) -> bool {
+    Path::new(filename)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| AUDIO_EXTENSIONS.iter().any(|known| known.eq_ignore_ascii_case(ext)))
+        .unwrap_or(false)
+}
+
+/// Finds every `tag_start`-opening tag in `field` and pulls out `attr`'s
+/// value, e.g. `scan_attr_values(html, "<img", "src")` for `<img src="x">`.
+fn scan_attr_values<'a>(field: &'a str, tag_start: &str, attr: &str) -> Vec<&'a str> {
+    let mut out = Vec::new();
+    let mut rest = field;
+    while let Some(start) = rest.find(tag_start) {
+        let after = &rest[start..];
+        let Some(tag_end) = after.find('>') else { break };
+        let tag = &after[..tag_end];
+        if let Some(value) = attr_value(tag, attr) {
+            out.push(value);
+        }
+        rest = &after[tag_end + 1..];
+    }
+    out
+}
+
+fn attr_value<'a>(tag: &'a str, attr: &str) -> Option<&'a str> {
+    let needle = format!("{}=\"", attr);
+    let start = tag.find(&needle)? + needle.len();
+    let rest = &tag[start..];
+    let end = rest.find('"')?;
+    Some(&rest[..end])
+}
+
+/// Finds every `[sound:filename]` reference in `field`.
+fn scan_sound_refs(field: &str) -> Vec<&str> {
+    let mut out = Vec::new();
+    let mut rest = field;
+    while let Some(start) = rest.find("[sound:") {
+        let after = &rest[start + "[sound:".len()..];
+        let Some(end) = after.find(']') else { break };
+        out.push(&after[..end]);
+        rest = &after[end + 1..];
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sanitized_file_name_strips_parent_dir_traversal() {
+        assert_eq!(sanitized_file_name("../../../etc/passwd"), Some("passwd".to_string()));
+        assert_eq!(sanitized_file_name("../../evil.mp3"), Some("evil.mp3".to_string()));
+    }
+
+    #[test]
+    fn sanitized_file_name_strips_absolute_paths() {
+        assert_eq!(sanitized_file_name("/etc/passwd"), Some("passwd".to_string()));
+    }
+
+    #[test]
+    fn sanitized_file_name_rejects_bare_dot_entries() {
+        assert_eq!(sanitized_file_name(".."), None);
+        assert_eq!(sanitized_file_name("."), None);
+        assert_eq!(sanitized_file_name(""), None);
+    }
+
+    #[test]
+    fn sanitized_file_name_keeps_a_plain_name() {
+        assert_eq!(sanitized_file_name("dog.jpg"), Some("dog.jpg".to_string()));
+    }
+}
@@ -4,9 +4,13 @@
 // Make the loader module public so other parts of our application can use it.
 pub mod loader;
 pub mod html_parser;
+pub mod media;
+pub mod sync;
 
 use std::collections::HashMap;
 
+pub use media::MediaRef;
+
 /// Represents a single Anki card.
 /// We use `#[derive(Debug)]` to allow for easy printing to the console, which is great for debugging.
 #[derive(Debug, Clone)]
@@ -17,6 +21,10 @@ pub struct Card {
     pub interval: u32,   // Interval in days
     pub ease_factor: u32, // The ease factor (stored as an integer in Anki DB)
     pub lapses: u32,     // Number of times the card has been forgotten
+    // FSRS-specific state. `None` means the card has never been scheduled under
+    // FSRS, in which case schedulers fall back to the SM-2 fields above.
+    pub stability: Option<f64>,
+    pub difficulty: Option<f64>,
 }
 
 /// Represents a single Anki note, which contains the actual content (front, back, etc.).
@@ -24,7 +32,12 @@ pub struct Card {
 pub struct Note {
     pub id: i64,
     // A vector of strings, where each string is a field (e.g., fields[0] is Front, fields[1] is Back).
-    pub fields: Vec<String>, 
+    pub fields: Vec<String>,
+    // Images and audio referenced by `<img src="...">`/`[sound:...]` across
+    // all of this note's fields, already resolved to extracted files on
+    // disk. Empty for notes with no media or loaded outside `load_apkg`
+    // (e.g. the scheduler's test decks).
+    pub media: Vec<MediaRef>,
 }
 
 /// Represents the entire deck collection.
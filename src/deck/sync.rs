@@ -0,0 +1,231 @@
+// src/deck/sync.rs
+// Reconciles a freshly-parsed deck against previously persisted scheduling
+// state, mirroring the flashcards project's synchronize step.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+use super::{Card, Deck};
+use crate::storage::DatabaseManager;
+
+/// Counts of what a `sync_deck` pass changed, so a front-end can report it.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SyncSummary {
+    pub added: usize,
+    pub kept: usize,
+    pub hidden: usize,
+}
+
+/// If `deck_path` is newer than the last recorded sync, reconciles `deck`
+/// against the state already in `db`: new notes enter scheduling, notes that
+/// still exist (matched by id, falling back to a field hash if the id
+/// changed) keep their interval/ease/stability, and notes that disappeared
+/// have their cards marked hidden rather than losing their review history.
+pub fn sync_deck(deck_path: &Path, deck: &Deck, db: &DatabaseManager) -> Result<SyncSummary, String> {
+    let modified = mtime_secs(deck_path)?;
+    if let Some(last_synced) = db.last_synced_at().map_err(|e| e.to_string())? {
+        if modified <= last_synced {
+            return Ok(SyncSummary::default());
+        }
+    }
+
+    let known = db.load_card_states().map_err(|e| e.to_string())?;
+    let fresh_ids: HashSet<i64> = deck.cards.iter().map(|c| c.id).collect();
+    // Maps a note's field hash to the fresh cards carrying it, in card-id
+    // order, so a note that reappeared under a new id can be found from the
+    // old row's `note_hash` below. A note can own more than one card (e.g.
+    // Basic+reversed, multi-ordinal cloze), so this has to be a Vec rather
+    // than a single entry, or siblings sharing a hash would collide.
+    let mut fresh_card_by_hash: HashMap<i64, Vec<(i64, i64)>> = HashMap::new();
+    for c in &deck.cards {
+        let hash = deck.notes.get(&c.note_id).map(field_hash_note).unwrap_or(0);
+        fresh_card_by_hash.entry(hash).or_default().push((c.id, c.note_id));
+    }
+    for group in fresh_card_by_hash.values_mut() {
+        group.sort_unstable_by_key(|&(id, _)| id);
+    }
+    // Mirrors the grouping above for the stale side, so a stale card's
+    // position among its own note-hash siblings (its "ordinal") can be
+    // looked up and matched to the fresh sibling at the same position,
+    // rather than every sibling racing for the same fresh card.
+    let mut known_by_hash: HashMap<i64, Vec<i64>> = HashMap::new();
+    for (&card_id, state) in known.iter() {
+        if let Some(h) = state.note_hash {
+            known_by_hash.entry(h).or_default().push(card_id);
+        }
+    }
+    for group in known_by_hash.values_mut() {
+        group.sort_unstable();
+    }
+
+    let mut summary = SyncSummary::default();
+    for card in &deck.cards {
+        if known.contains_key(&card.id) {
+            summary.kept += 1;
+        } else {
+            summary.added += 1;
+        }
+        let note_hash = deck.notes.get(&card.note_id).map(field_hash_note).unwrap_or(0);
+        db.upsert_synced_card(card, note_hash).map_err(|e| e.to_string())?;
+    }
+
+    for (&card_id, state) in known.iter() {
+        if fresh_ids.contains(&card_id) || state.hidden {
+            continue;
+        }
+        let matched = state.note_hash.and_then(|h| {
+            let ordinal = known_by_hash.get(&h)?.iter().position(|&id| id == card_id)?;
+            fresh_card_by_hash.get(&h)?.get(ordinal).copied()
+        });
+        if let Some((new_card_id, new_note_id)) = matched {
+            // A note with identical fields reappeared under a different id;
+            // the new id was already inserted above as a fresh, unscheduled
+            // card, so carry the old row's scheduling state across onto it
+            // before hiding the stale row, rather than losing it.
+            let carried = Card {
+                id: new_card_id,
+                note_id: new_note_id,
+                due: state.due,
+                interval: state.interval,
+                ease_factor: state.ease_factor,
+                lapses: state.lapses,
+                stability: state.stability,
+                difficulty: state.difficulty,
+            };
+            db.update_card_state(&carried).map_err(|e| e.to_string())?;
+            db.mark_card_hidden(card_id, true).map_err(|e| e.to_string())?;
+            summary.hidden += 1;
+            continue;
+        }
+        db.mark_card_hidden(card_id, true).map_err(|e| e.to_string())?;
+        summary.hidden += 1;
+    }
+
+    db.set_last_synced_at(modified).map_err(|e| e.to_string())?;
+    Ok(summary)
+}
+
+fn mtime_secs(path: &Path) -> Result<i64, String> {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .map_err(|e| e.to_string())?
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| e.to_string())
+        .map(|d| d.as_secs() as i64)
+}
+
+fn field_hash_note(note: &super::Note) -> i64 {
+    field_hash(&note.fields)
+}
+
+fn field_hash(fields: &[String]) -> i64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    fields.hash(&mut hasher);
+    hasher.finish() as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deck::Note;
+
+    fn temp_deck_id(name: &str) -> String {
+        format!("test_sync_{}_{}", name, std::process::id())
+    }
+
+    fn deck_file_path(deck_id: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("{}.apkg", deck_id))
+    }
+
+    fn note(id: i64, text: &str) -> Note {
+        Note { id, fields: vec![text.to_string()], media: Vec::new() }
+    }
+
+    fn card(id: i64, note_id: i64) -> Card {
+        Card { id, note_id, due: 0, interval: 0, ease_factor: 2500, lapses: 0, stability: None, difficulty: None }
+    }
+
+    fn deck_of(cards: Vec<Card>, notes: Vec<Note>) -> Deck {
+        Deck { cards, notes: notes.into_iter().map(|n| (n.id, n)).collect() }
+    }
+
+    #[test]
+    fn test_reappeared_note_carries_scheduling_state_to_new_card_id() {
+        let deck_id = temp_deck_id("reappear");
+        let deck_path = deck_file_path(&deck_id);
+        fs::write(&deck_path, "v1").unwrap();
+        let db = DatabaseManager::new(&deck_id).unwrap();
+
+        let old_deck = deck_of(vec![card(1, 10)], vec![note(10, "Front::Back")]);
+        sync_deck(&deck_path, &old_deck, &db).unwrap();
+
+        // Simulate the card having been reviewed before the note's id churned.
+        let mut reviewed = card(1, 10);
+        reviewed.due = 42;
+        reviewed.interval = 7;
+        reviewed.ease_factor = 2600;
+        db.update_card_state(&reviewed).unwrap();
+
+        // Force the next sync_deck call to actually run a reconciliation pass
+        // even though the deck file's mtime hasn't changed since the first call.
+        db.set_last_synced_at(0).unwrap();
+
+        // The note reappears under a new note/card id with identical fields.
+        let new_deck = deck_of(vec![card(2, 20)], vec![note(20, "Front::Back")]);
+        let summary = sync_deck(&deck_path, &new_deck, &db).unwrap();
+
+        assert_eq!(summary.hidden, 1);
+        let states = db.load_card_states().unwrap();
+        assert!(states[&1].hidden);
+        let carried = &states[&2];
+        assert_eq!(carried.due, 42);
+        assert_eq!(carried.interval, 7);
+        assert_eq!(carried.ease_factor, 2600);
+
+        fs::remove_file(&deck_path).ok();
+        fs::remove_file(Path::new("anki/history").join(format!("{}.db", deck_id))).ok();
+    }
+
+    #[test]
+    fn test_multi_card_note_siblings_match_distinct_fresh_cards() {
+        let deck_id = temp_deck_id("siblings");
+        let deck_path = deck_file_path(&deck_id);
+        fs::write(&deck_path, "v1").unwrap();
+        let db = DatabaseManager::new(&deck_id).unwrap();
+
+        // A note with two cards (e.g. Basic+reversed) shares one field hash
+        // across both of its cards; a bare hash->card map would let the
+        // second card's match clobber the first's.
+        let old_deck = deck_of(vec![card(1, 10), card(2, 10)], vec![note(10, "Front::Back")]);
+        sync_deck(&deck_path, &old_deck, &db).unwrap();
+
+        let mut first = card(1, 10);
+        first.due = 10;
+        first.interval = 1;
+        db.update_card_state(&first).unwrap();
+        let mut second = card(2, 10);
+        second.due = 20;
+        second.interval = 2;
+        db.update_card_state(&second).unwrap();
+
+        db.set_last_synced_at(0).unwrap();
+
+        let new_deck = deck_of(vec![card(3, 20), card(4, 20)], vec![note(20, "Front::Back")]);
+        let summary = sync_deck(&deck_path, &new_deck, &db).unwrap();
+
+        assert_eq!(summary.hidden, 2);
+        let states = db.load_card_states().unwrap();
+        // Each stale sibling must land on its own fresh card, keyed by its
+        // ordinal among same-hash siblings, not both racing for one slot.
+        assert_eq!(states[&3].due, 10);
+        assert_eq!(states[&3].interval, 1);
+        assert_eq!(states[&4].due, 20);
+        assert_eq!(states[&4].interval, 2);
+
+        fs::remove_file(&deck_path).ok();
+        fs::remove_file(Path::new("anki/history").join(format!("{}.db", deck_id))).ok();
+    }
+}
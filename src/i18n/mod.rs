@@ -0,0 +1,207 @@
+// src/i18n/mod.rs
+// Localization subsystem: per-language string tables bundled at compile time,
+// with English used as the fallback for any key a language table is missing.
+
+use std::collections::HashMap;
+
+/// Stable identifier for every user-facing string in the app. Adding a new
+/// piece of UI text means adding a variant here and a matching key in every
+/// `lang/*.toml` table.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum StringId {
+    LoadingDeck,
+    DeckComplete,
+    SelectADeck,
+    ReturnToMainMenu,
+    NoDecksFound,
+    NoDecksInstructions,
+    NoMatchingDecks,
+    SearchLabel,
+    MenuTitle,
+    MenuStudy,
+    MenuProfile,
+    MenuMusic,
+    MenuControls,
+    MenuSettings,
+    MenuQuit,
+    JukeboxTitle,
+    NoMusicFound,
+    NoMusicInstructions,
+    NowPlaying,
+    JukeboxHint,
+    ErrorPrefix,
+    NoDecksFoundInDirectory,
+    StudyHintGamepad,
+    StudyHintKeyboard,
+    RatingAgain,
+    RatingHard,
+    RatingGood,
+    RatingEasy,
+    SessionMoreDueToday,
+    SessionDueTomorrow,
+    SessionDueInDays,
+    SessionNoMoreScheduled,
+    ForecastNextIn,
+    ForecastLearning,
+    ForecastReviewLeft,
+    OptionsTitle,
+    OptionFontScale,
+    OptionSfxVolume,
+    OptionBgmVolume,
+    OptionShowRuby,
+    OptionNewCardsPerDay,
+    OptionRatingButtons,
+    ToggleOn,
+    ToggleOff,
+    RatingButtonsStandard,
+    RatingButtonsSwapped,
+    ControlsTitle,
+    ConflictReserved,
+    ConflictAlreadyBound,
+    SettingsTitle,
+    SettingsSfx,
+    SettingsMenuSound,
+    SettingsPauseOnFocusLoss,
+    SettingsJapanese,
+}
+
+impl StringId {
+    /// The TOML key this variant is looked up under.
+    fn key(self) -> &'static str {
+        match self {
+            StringId::LoadingDeck => "loading_deck",
+            StringId::DeckComplete => "deck_complete",
+            StringId::SelectADeck => "select_a_deck",
+            StringId::ReturnToMainMenu => "return_to_main_menu",
+            StringId::NoDecksFound => "no_decks_found",
+            StringId::NoDecksInstructions => "no_decks_instructions",
+            StringId::NoMatchingDecks => "no_matching_decks",
+            StringId::SearchLabel => "search_label",
+            StringId::MenuTitle => "menu_title",
+            StringId::MenuStudy => "menu_study",
+            StringId::MenuProfile => "menu_profile",
+            StringId::MenuMusic => "menu_music",
+            StringId::MenuControls => "menu_controls",
+            StringId::MenuSettings => "menu_settings",
+            StringId::MenuQuit => "menu_quit",
+            StringId::JukeboxTitle => "jukebox_title",
+            StringId::NoMusicFound => "no_music_found",
+            StringId::NoMusicInstructions => "no_music_instructions",
+            StringId::NowPlaying => "now_playing",
+            StringId::JukeboxHint => "jukebox_hint",
+            StringId::ErrorPrefix => "error_prefix",
+            StringId::NoDecksFoundInDirectory => "no_decks_found_in_directory",
+            StringId::StudyHintGamepad => "study_hint_gamepad",
+            StringId::StudyHintKeyboard => "study_hint_keyboard",
+            StringId::RatingAgain => "rating_again",
+            StringId::RatingHard => "rating_hard",
+            StringId::RatingGood => "rating_good",
+            StringId::RatingEasy => "rating_easy",
+            StringId::SessionMoreDueToday => "session_more_due_today",
+            StringId::SessionDueTomorrow => "session_due_tomorrow",
+            StringId::SessionDueInDays => "session_due_in_days",
+            StringId::SessionNoMoreScheduled => "session_no_more_scheduled",
+            StringId::ForecastNextIn => "forecast_next_in",
+            StringId::ForecastLearning => "forecast_learning",
+            StringId::ForecastReviewLeft => "forecast_review_left",
+            StringId::OptionsTitle => "options_title",
+            StringId::OptionFontScale => "option_font_scale",
+            StringId::OptionSfxVolume => "option_sfx_volume",
+            StringId::OptionBgmVolume => "option_bgm_volume",
+            StringId::OptionShowRuby => "option_show_ruby",
+            StringId::OptionNewCardsPerDay => "option_new_cards_per_day",
+            StringId::OptionRatingButtons => "option_rating_buttons",
+            StringId::ToggleOn => "toggle_on",
+            StringId::ToggleOff => "toggle_off",
+            StringId::RatingButtonsStandard => "rating_buttons_standard",
+            StringId::RatingButtonsSwapped => "rating_buttons_swapped",
+            StringId::ControlsTitle => "controls_title",
+            StringId::ConflictReserved => "conflict_reserved",
+            StringId::ConflictAlreadyBound => "conflict_already_bound",
+            StringId::SettingsTitle => "settings_title",
+            StringId::SettingsSfx => "settings_sfx",
+            StringId::SettingsMenuSound => "settings_menu_sound",
+            StringId::SettingsPauseOnFocusLoss => "settings_pause_on_focus_loss",
+            StringId::SettingsJapanese => "settings_japanese",
+        }
+    }
+}
+
+const FALLBACK_LANGUAGE: &str = "en";
+
+const EN_TABLE: &str = include_str!("lang/en.toml");
+const JA_TABLE: &str = include_str!("lang/ja.toml");
+
+/// Holds every bundled language table plus the currently active language.
+/// Layouts built from translated strings (`layout_text_binary`,
+/// `render_text_to_surface`) are cached, so anything that changes the active
+/// language must bump `version` and scenes must re-layout once they notice it
+/// moved.
+pub struct Translations {
+    active: String,
+    tables: HashMap<String, HashMap<String, String>>,
+    version: u32,
+}
+
+impl Translations {
+    /// Parses every bundled `lang/*.toml` table and selects `active_language`
+    /// (falling back to English if it names a language we don't have).
+    pub fn load(active_language: &str) -> Result<Self, String> {
+        let mut tables = HashMap::new();
+        tables.insert(FALLBACK_LANGUAGE.to_string(), parse_table(EN_TABLE)?);
+        tables.insert("ja".to_string(), parse_table(JA_TABLE)?);
+
+        let active = if tables.contains_key(active_language) {
+            active_language.to_string()
+        } else {
+            FALLBACK_LANGUAGE.to_string()
+        };
+
+        Ok(Self { active, tables, version: 0 })
+    }
+
+    /// Returns the active-language string for `id`, falling back to the
+    /// English entry when the active table is missing it.
+    pub fn tr(&self, id: StringId) -> &str {
+        let key = id.key();
+        self.tables
+            .get(&self.active)
+            .and_then(|table| table.get(key))
+            .or_else(|| self.tables.get(FALLBACK_LANGUAGE).and_then(|table| table.get(key)))
+            .map(|s| s.as_str())
+            .unwrap_or(key)
+    }
+
+    /// Like [`tr`](Self::tr), but substitutes every `{name}` placeholder in
+    /// the looked-up string with its matching value from `params`, so
+    /// translated strings can embed runtime data (an error message, a path)
+    /// without the translator needing to know `format!`'s syntax.
+    pub fn tr_with(&self, id: StringId, params: &[(&str, &str)]) -> String {
+        let mut text = self.tr(id).to_string();
+        for (name, value) in params {
+            text = text.replace(&format!("{{{}}}", name), value);
+        }
+        text
+    }
+
+    pub fn language(&self) -> &str {
+        &self.active
+    }
+
+    /// Switches the active language, bumping `version` so cached layouts
+    /// built from translated text know to rebuild.
+    pub fn set_language(&mut self, language: &str) {
+        if self.active != language && self.tables.contains_key(language) {
+            self.active = language.to_string();
+            self.version += 1;
+        }
+    }
+
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+}
+
+fn parse_table(contents: &str) -> Result<HashMap<String, String>, String> {
+    toml::from_str(contents).map_err(|e| e.to_string())
+}
@@ -0,0 +1,26 @@
+// src/input.rs
+// Controller-type detection, used to pick which `KeyBindings` default
+// profile applies before any `keybindings.toml` override is layered on top.
+
+/// Broad classification of an opened `GameController`, per doukutsu-rs's
+/// `ControllerType` idea: the reference Brick hardware reports itself with a
+/// fixed recognizable name and ships with its own A/B swap and magic
+/// joystick indices baked into [`crate::state::KeyBindings::default_bindings`];
+/// anything else is a generic SDL game controller that should get a
+/// straightforward, unswapped mapping instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ControllerType {
+    Brick,
+    Generic,
+}
+
+impl ControllerType {
+    /// Classifies a controller by its SDL-reported name.
+    pub fn detect(name: &str) -> Self {
+        if name.to_lowercase().contains("brick") {
+            ControllerType::Brick
+        } else {
+            ControllerType::Generic
+        }
+    }
+}
@@ -1,12 +1,14 @@
 // CardBrick - main.rs (Refactor Step 6: Deck Selection Scene)
 
-use crate::mixer::Channel;
-use crate::mixer::Chunk;
-use crate::state::Sfx;
 use std::io::Write;
 mod config;
 mod deck;
+mod i18n;
+mod input;
+mod menu;
 mod scheduler;
+mod settings;
+mod sound;
 mod ui;
 mod storage;
 mod debug;
@@ -14,27 +16,32 @@ mod scenes;
 mod state;
 
 use scenes::deck_selection::DeckSelectionState;
+use scenes::jukebox::JukeboxState;
 use std::fs;
 use std::path::{Path};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use sdl2::event::Event;
 use sdl2::keyboard::Keycode;
-use sdl2::pixels::Color;
 use sdl2::rect::Rect;
 
-use config::Config;
+use config::{Config, TimingMode};
+use i18n::Translations;
 use scheduler::{Scheduler, Sm2Scheduler};
 use ui::{CanvasManager, FontManager, font::TextLayout, sprite::Sprite};
 use deck::html_parser;
 use storage::{DatabaseManager, ReplayLogger};
 use scenes::main_menu::MainMenuState;
-use state::{LoaderMessage, DeckMetadata, AppState, GameState, BrickInput, BrickButton, map_to_brick_input};
+use input::ControllerType;
+use settings::Settings;
+use sound::{BgmTrack, Sfx, SoundManager};
+use state::{LoaderMessage, DeckMetadata, AppState, GameState, BrickInput, BrickButton, KeyBindings, map_to_brick_input};
 
 use sdl2::mixer::{self, InitFlag, AUDIO_S16LSB, DEFAULT_CHANNELS};
 
 pub fn main() -> Result<(), String> {
-    let config = Config::new();
+    let mut config = Config::new();
+    let settings = Settings::load(&config.settings_path);
 
     if let Err(e) = test_file_creation() {
         panic!("[File Creation Test] FAILED with error: {}", e);
@@ -42,36 +49,45 @@ pub fn main() -> Result<(), String> {
 
     let sdl_context = sdl2::init()?;
     let video_subsystem = sdl_context.video()?;
-    let _mixer_context = mixer::init(InitFlag::MP3 | InitFlag::FLAC | InitFlag::MOD)?;
+    let mixer_ctx = mixer::init(InitFlag::MP3 | InitFlag::FLAC | InitFlag::MOD)?;
     mixer::open_audio(44_100, AUDIO_S16LSB, DEFAULT_CHANNELS, 1_024)?;
     mixer::allocate_channels(4);
 
     let _audio_subsystem = sdl_context.audio()?;
 
-    let sfx = Sfx{
-        up_down_sound: Chunk::from_file(config.sfx_directory.join("click.wav"))?,
-        open_sound: Chunk::from_file(config.sfx_directory.join("open.wav"))?,
-        mixer_ctx: _mixer_context
-    };
+    let mut sound = SoundManager::new(&config, mixer_ctx)?;
+    sound.set_sfx_volume(settings.sfx_volume);
+    sound.set_music_volume(settings.bgm_volume);
+    sound.set_sfx_enabled(settings.sfx_enabled);
 
-    let card = Chunk::from_file(config.sfx_directory.join("card-shuffle.wav"))?;
+    let translations = Translations::load(&settings.language)?;
 
-    Channel::all().play(&card, 0)?;
+    sound.play_sfx(Sfx::CardShuffle);
 
 
     sdl2::hint::set("SDL_RENDER_SCALE_QUALITY", "1");
     let ttf_context = sdl2::ttf::init().map_err(|e| e.to_string())?;
+    let _image_context = sdl2::image::init(sdl2::image::InitFlag::PNG | sdl2::image::InitFlag::JPG)?;
 
     let window = video_subsystem.window(config.window_title, config.window_width, config.window_height).position_centered().build().map_err(|e| e.to_string())?;
-    let sdl_canvas = window.into_canvas().build().map_err(|e| e.to_string())?;
+    let canvas_builder = window.into_canvas();
+    let canvas_builder = if config.timing_mode == TimingMode::VSync {
+        canvas_builder.present_vsync()
+    } else {
+        canvas_builder
+    };
+    let sdl_canvas = canvas_builder.build().map_err(|e| e.to_string())?;
     let texture_creator = sdl_canvas.texture_creator();
 
+    // Enables `Event::TextInput`, which the deck-selection search box consumes.
+    video_subsystem.text_input().start();
+
     let available_decks = load_decks_from_directory(Path::new(&config.decks_directory))?;
 
     if available_decks.is_empty() {
-        return Err(format!(
-            "No .apkg decks found in the '{}' directory.",
-            config.decks_directory.display()
+        return Err(translations.tr_with(
+            i18n::StringId::NoDecksFoundInDirectory,
+            &[("directory", &config.decks_directory.display().to_string())],
         ));
     }
 
@@ -91,18 +107,44 @@ pub fn main() -> Result<(), String> {
         }
     }
 
+    // Pick the key bindings profile for whatever's actually plugged in: the
+    // reference Brick hardware needs its A/B swap and magic volume-button
+    // joystick indices, but a generic gamepad should get a straightforward
+    // mapping instead. Keyboard-only (no controller) keeps the Brick profile,
+    // matching this app's historical default.
+    let controller_type = controllers.first()
+        .map(|c| ControllerType::detect(&c.name()))
+        .unwrap_or(ControllerType::Brick);
+    config.key_bindings = KeyBindings::load_for(&config.keybindings_path, controller_type);
+
+    // Font managers are built from the active `DeviceProfile`'s tiers rather
+    // than hardcoded paths/sizes, so a smaller-screen profile with fewer
+    // tiers (e.g. no `sub`) falls back to `normal` instead of failing to load.
+    let profile = &config.profile;
+    let sub_tier = profile.sub.as_ref().unwrap_or(&profile.normal);
+    let mono_tier = profile.mono.as_ref().unwrap_or(&profile.normal);
+    // `Settings::font_scale` can't be applied live (the loaded `Font`s borrow
+    // `ttf_context`/`texture_creator`, both local to this function), so it's
+    // baked into each tier's point size once here; changing it in the options
+    // scene takes effect on the next launch.
+    let scale_font_size = |size: u32| ((size as f32) * settings.font_scale).round().max(1.0) as u16;
+
     let mut app_state = AppState {
-        game_state: GameState::MainMenu(MainMenuState::new()),
+        game_state: GameState::MainMenu(MainMenuState::new(&translations)),
         available_decks,
         canvas_manager: CanvasManager::new(sdl_canvas, &texture_creator)?,
-        font_manager: FontManager::new(&ttf_context, &config.font_path, config.font_size_large.try_into().unwrap())?,
-        small_font_manager: FontManager::new(&ttf_context, &config.font_path, config.font_size_medium.try_into().unwrap())?,
-        hint_font_manager: FontManager::new_with_fallback(&ttf_context,  
-            &config.command_font_path, Some(&config.emoji_font_path), config.font_size_small.try_into().unwrap())?,
-        sprite: Sprite::new(),
+        font_manager: FontManager::new(&ttf_context, &profile.normal.path, scale_font_size(profile.normal.size), &texture_creator)?,
+        small_font_manager: FontManager::new(&ttf_context, &sub_tier.path, scale_font_size(sub_tier.size), &texture_creator)?,
+        hint_font_manager: FontManager::new_with_fallback(&ttf_context,
+            &mono_tier.path, Some(&profile.normal.path), scale_font_size(mono_tier.size), &texture_creator)?,
+        media_cache: ui::MediaCache::new(&texture_creator),
+        sprite: Sprite::new(&config.sprite_sheet_path, &texture_creator),
         config,
         controllers: controllers,
-        sfx: sfx,
+        gc_subsystem,
+        sound,
+        translations,
+        settings,
     };
     
     run(&mut app_state, &mut sdl_context.event_pump()?)
@@ -125,11 +167,22 @@ fn load_decks_from_directory(dir_path: &Path) -> Result<Vec<DeckMetadata>, Strin
                         .and_then(|s| s.to_str())
                         .unwrap_or("unknown_deck")
                         .to_string();
-                    let deck_name = deck_id.clone(); // Or you could implement logic to read the name from the .apkg file
+                    let today = scheduler::current_day_number();
+                    let (deck_name, total_count, new_count, due_count) =
+                        match deck::loader::read_deck_summary(&path, today) {
+                            Ok(summary) => (summary.name, summary.total_count, summary.new_count, summary.due_count),
+                            Err(e) => {
+                                println!("Failed to read deck summary for '{}': {}", path.display(), e);
+                                (deck_id.clone(), 0, 0, 0)
+                            }
+                        };
                     decks.push(DeckMetadata {
                         id: deck_id,
                         name: deck_name,
                         path: path.clone(),
+                        total_count,
+                        new_count,
+                        due_count,
                     });
                 }
             }
@@ -140,19 +193,106 @@ fn load_decks_from_directory(dir_path: &Path) -> Result<Vec<DeckMetadata>, Strin
 
 
 fn run(state: &mut AppState, event_pump: &mut sdl2::EventPump) -> Result<(), String> {
+    let timestep = state.config.timing_mode.timestep();
+    let mut accumulator = Duration::ZERO;
+    let mut last_time = Instant::now();
+    // Tracks window focus so `Settings::pause_on_focus_loss` can freeze the
+    // update loop while the window is in the background instead of burning
+    // through reviews/animation the player isn't looking at.
+    let mut focused = true;
+
     'running: loop {
         for event in event_pump.poll_iter() {
-            if let Event::Quit { .. } | Event::KeyDown { keycode: Some(Keycode::Escape), .. } = event {
-                break 'running;
+            // The controls menu's "press Escape to cancel" capture mode needs
+            // Escape to reach `handle_input` instead of being swallowed here
+            // as a global quit.
+            let capturing_key = matches!(
+                &state.game_state,
+                GameState::ControlsMenu(controls_menu) if controls_menu.capturing.is_some()
+            );
+
+            match event {
+                Event::Quit { .. } => break 'running,
+                Event::KeyDown { keycode: Some(Keycode::Escape), .. } if !capturing_key => break 'running,
+                Event::ControllerDeviceAdded { which, .. } => {
+                    if state.gc_subsystem.is_game_controller(which) {
+                        match state.gc_subsystem.open(which) {
+                            Ok(controller) => {
+                                let instance_id = controller.instance_id();
+                                // SDL also queues `ControllerDeviceAdded` for
+                                // controllers that were already open at
+                                // startup, the first time `poll_iter` runs;
+                                // drop the duplicate handle instead of
+                                // tracking the same physical pad twice.
+                                if state.controllers.iter().any(|c| c.instance_id() == instance_id) {
+                                    log::debug!("ignoring duplicate ControllerDeviceAdded for already-open controller {}", instance_id);
+                                } else {
+                                    log::debug!("controller hot-plugged {}: {:?}", which, controller.name());
+                                    // Re-derive the active bindings from the
+                                    // newly-connected pad, the same way
+                                    // startup does from `controllers.first()`,
+                                    // so a pad plugged in after launch isn't
+                                    // stuck on whatever profile was detected
+                                    // before it existed.
+                                    let controller_type = ControllerType::detect(&controller.name());
+                                    state.config.key_bindings =
+                                        KeyBindings::load_for(&state.config.keybindings_path, controller_type);
+                                    state.controllers.push(controller);
+                                }
+                            }
+                            Err(e) => log::warn!("failed to open hot-plugged controller {}: {}", which, e),
+                        }
+                    }
+                }
+                Event::ControllerDeviceRemoved { which, .. } => {
+                    state.controllers.retain(|c| c.instance_id() as i32 != which);
+                }
+                Event::Window { win_event: sdl2::event::WindowEvent::FocusLost, .. } => focused = false,
+                Event::Window { win_event: sdl2::event::WindowEvent::FocusGained, .. } => focused = true,
+                _ => handle_input(state, event)?,
+            }
+        }
+
+        let now = Instant::now();
+        let elapsed = now - last_time;
+        last_time = now;
+
+        // While unfocused with the setting on, skip `update_state` (reviews,
+        // animation) but keep drawing so the window doesn't go blank.
+        let paused = !focused && state.settings.pause_on_focus_loss;
+
+        match timestep {
+            Some(step) => {
+                // Fixed-timestep catch-up: a slow frame (e.g. the loading
+                // scene's deck parse) runs `update_state`/`Sprite::update`
+                // as many times as it takes to stay caught up with wall
+                // clock, instead of the animation stalling then jumping.
+                if !paused {
+                    accumulator += elapsed;
+                    while accumulator >= step {
+                        accumulator -= step;
+                        update_state(state)?;
+                        state.sprite.update();
+                    }
+                }
+                draw_scene(state)?;
+                // No vsync to pace us at this cadence, so sleep off whatever
+                // of the timestep this frame didn't already spend.
+                let since_last = Instant::now() - last_time;
+                if since_last < step {
+                    ::std::thread::sleep(step - since_last);
+                }
+            }
+            None => {
+                // VSync: one update per drawn frame; `present_vsync()` blocks
+                // `draw_scene`'s canvas swap until the next vblank.
+                if !paused {
+                    update_state(state)?;
+                    state.sprite.update();
+                }
+                draw_scene(state)?;
             }
-            handle_input(state, event)?;
         }
-        
-        update_state(state)?;
-        state.sprite.update();
-        draw_scene(state)?;
-        
-        ::std::thread::sleep(Duration::new(0, 1_000_000_000u32 / 60));
     }
     Ok(())
 }
@@ -160,7 +300,7 @@ fn run(state: &mut AppState, event_pump: &mut sdl2::EventPump) -> Result<(), Str
 fn handle_input(state: &mut AppState, event: Event) -> Result<(), String> {
 
     // These controls are consistent throughout the app.
-    if let Some(input) = map_to_brick_input(&event) {
+    if let Some(input) = map_to_brick_input(&event, &mut state.config.key_bindings) {
          match input {
              BrickInput::ButtonDown(BrickButton::Guide) => return Err("User quit".into()),
              _ => {}
@@ -171,27 +311,32 @@ fn handle_input(state: &mut AppState, event: Event) -> Result<(), String> {
         GameState::MainMenu(_) => scenes::main_menu::input::handle_main_menu_input(state, event),
         GameState::DeckSelection(_) => scenes::deck_selection::input::handle_deck_selection_input(state, event),
         GameState::Studying(_) => scenes::studying::input::handle_studying_input(state, event),
+        GameState::Jukebox(_) => scenes::jukebox::input::handle_jukebox_input(state, event),
+        GameState::Options(_) => scenes::options::input::handle_options_input(state, event),
+        GameState::ControlsMenu(_) => scenes::controls_menu::input::handle_controls_menu_input(state, event),
+        GameState::Settings(_) => scenes::settings::input::handle_settings_input(state, event),
         _ => Ok(()),
     }
 }
 
 fn draw_scene(state: &mut AppState) -> Result<(), String> {
-    state.canvas_manager.start_frame()?;
+    let theme = &state.config.theme;
+    state.canvas_manager.start_frame(theme.background)?;
     state.canvas_manager.with_canvas(|canvas| {
         match &mut state.game_state {
             GameState::MainMenu(main_menu_state) => {
-                scenes::main_menu::draw_main_menu_scene(canvas, &mut state.font_manager, main_menu_state)
+                scenes::main_menu::draw_main_menu_scene(canvas, &mut state.font_manager, main_menu_state, &state.translations, theme)
             },
             GameState::DeckSelection(deck_selection_state) => {
-                scenes::deck_selection::draw_deck_selection_scene(canvas, &mut state.font_manager, &mut state.small_font_manager, deck_selection_state)
+                scenes::deck_selection::draw_deck_selection_scene(canvas, &mut state.font_manager, &mut state.small_font_manager, deck_selection_state, &state.translations, theme)
             },
             GameState::Loading { loading_layout, progress, .. } => {
-                draw_loading_scene(canvas, &mut state.font_manager, loading_layout, *progress)
+                draw_loading_scene(canvas, &mut state.font_manager, loading_layout, *progress, theme)
             },
             GameState::Studying(studying_state) => {
-                scenes::studying::draw_studying_scene(canvas, studying_state, &mut state.font_manager, &mut state.small_font_manager, &mut state.hint_font_manager, &mut state.sprite)
+                scenes::studying::draw_studying_scene(canvas, studying_state, &mut state.font_manager, &mut state.small_font_manager, &mut state.hint_font_manager, &mut state.sprite, &mut state.media_cache, theme, &state.translations)
             },
-            GameState::Error(e) => draw_error_scene(canvas, &mut state.font_manager, e),
+            GameState::Error(e) => draw_error_scene(canvas, &mut state.font_manager, e, &state.translations, theme),
             GameState::GoToDeckSelection => {
                 let new_state = DeckSelectionState::new(
                     state.available_decks.clone(),
@@ -201,6 +346,30 @@ fn draw_scene(state: &mut AppState) -> Result<(), String> {
                 state.game_state = GameState::DeckSelection(new_state);
                 Ok(()) // <-- Add this line
             }
+            GameState::GoToJukebox => {
+                state.game_state = GameState::Jukebox(JukeboxState::new(&state.config));
+                Ok(())
+            }
+            GameState::Jukebox(jukebox_state) => {
+                scenes::jukebox::draw_jukebox_scene(
+                    canvas,
+                    &mut state.font_manager,
+                    &mut state.small_font_manager,
+                    jukebox_state,
+                    state.sound.now_playing_name(),
+                    &state.translations,
+                    theme,
+                )
+            }
+            GameState::Options(options_state) => {
+                scenes::options::draw_options_scene(canvas, &mut state.font_manager, options_state, &state.settings, &state.translations, theme)
+            }
+            GameState::ControlsMenu(controls_menu_state) => {
+                scenes::controls_menu::draw_controls_menu_scene(canvas, &mut state.font_manager, controls_menu_state, &state.translations, theme)
+            }
+            GameState::Settings(settings_state) => {
+                scenes::settings::draw_settings_scene(canvas, &mut state.font_manager, settings_state, &state.translations, theme)
+            }
 
         }
     })?;
@@ -211,23 +380,75 @@ fn draw_scene(state: &mut AppState) -> Result<(), String> {
 fn update_state(state: &mut AppState) -> Result<(), String> {
     let old_state = std::mem::replace(&mut state.game_state, GameState::Error("Temporary state".to_string()));
     state.game_state = match old_state {
-        GameState::Loading { rx, loading_layout, progress, deck_id_to_load } => {
+        GameState::Loading { rx, loading_layout, progress, deck_id_to_load, deck_path } => {
             if let Ok(msg) = rx.try_recv() {
                 match msg {
                     LoaderMessage::Complete(Ok(deck)) => {
-                        let scheduler = Box::new(Sm2Scheduler::new(deck));
                         let db_manager = DatabaseManager::new(&deck_id_to_load).map_err(|e| e.to_string())?;
+                        let sync_summary = deck::sync::sync_deck(&deck_path, &deck, &db_manager)?;
+                        println!(
+                            "Synced deck {}: {} added, {} kept, {} hidden",
+                            deck_id_to_load, sync_summary.added, sync_summary.kept, sync_summary.hidden
+                        );
                         let replay_logger = ReplayLogger::new(&deck_id_to_load).map_err(|e| e.to_string())?;
+                        // Re-apply any review the app crashed between logging and
+                        // committing to SQLite for, before the scheduler reads
+                        // card state back out of the database.
+                        let replayed = replay_logger.recover(&db_manager)?;
+                        if replayed > 0 {
+                            println!("Recovered {} review(s) from the transaction log for deck {}", replayed, deck_id_to_load);
+                        }
+                        let scheduler = Box::new(Sm2Scheduler::new_from_db_with_limit(
+                            deck,
+                            &db_manager,
+                            scheduler::current_day_number(),
+                            state.settings.new_cards_per_day as usize,
+                        ));
                         let mut studying_state = scenes::studying::StudyingState::new(scheduler, db_manager, replay_logger);
-                        scenes::studying::logic::load_next_card(&mut studying_state, &mut state.font_manager, &mut state.small_font_manager);
+                        studying_state.show_ruby_text = state.settings.show_ruby_by_default;
+                        scenes::studying::logic::load_next_card(&mut studying_state, &mut state.font_manager, &mut state.small_font_manager, &state.translations, &state.config);
                         GameState::Studying(studying_state)
                     }
                     LoaderMessage::Complete(Err(e)) => GameState::Error(e),
-                    LoaderMessage::Progress(p) => GameState::Loading { rx, loading_layout, progress: p, deck_id_to_load },
+                    LoaderMessage::Progress(p) => GameState::Loading { rx, loading_layout, progress: p, deck_id_to_load, deck_path },
                 }
             } else {
-                GameState::Loading { rx, loading_layout, progress, deck_id_to_load }
+                GameState::Loading { rx, loading_layout, progress, deck_id_to_load, deck_path }
+            }
+        }
+        GameState::DeckSelection(mut deck_selection_state) => {
+            deck_selection_state.apply_axis_scroll();
+            if deck_selection_state.apply_dpad_repeat() {
+                state.sound.play_sfx(Sfx::UpDown);
+            }
+            GameState::DeckSelection(deck_selection_state)
+        }
+        GameState::Studying(mut studying_state) => {
+            studying_state.apply_axis_scroll();
+            state.sound.play_bgm(BgmTrack::Studying);
+            GameState::Studying(studying_state)
+        }
+        GameState::MainMenu(mut main_menu_state) => {
+            state.sound.play_bgm(BgmTrack::MainMenu);
+            main_menu_state.refresh_if_stale(&state.translations);
+            // A held D-pad direction doesn't generate its own repeated
+            // `ButtonDown`s, so `Menu::tick` drives auto-repeat once per step.
+            if main_menu_state.menu.tick() && state.settings.menu_sound_enabled {
+                state.sound.play_sfx(Sfx::UpDown);
+            }
+            GameState::MainMenu(main_menu_state)
+        }
+        GameState::ControlsMenu(mut controls_menu_state) => {
+            if controls_menu_state.menu.tick() {
+                state.sound.play_sfx(Sfx::UpDown);
+            }
+            GameState::ControlsMenu(controls_menu_state)
+        }
+        GameState::Settings(mut settings_state) => {
+            if settings_state.menu.tick() {
+                state.sound.play_sfx(Sfx::UpDown);
             }
+            GameState::Settings(settings_state)
         }
         other_state => other_state,
     };
@@ -235,21 +456,24 @@ fn update_state(state: &mut AppState) -> Result<(), String> {
 }
 
 
-fn draw_loading_scene(canvas: &mut sdl2::render::Canvas<sdl2::video::Window>, font_manager: &mut FontManager, layout: &TextLayout, progress: f32) -> Result<(), String> {
+fn draw_loading_scene(canvas: &mut sdl2::render::Canvas<sdl2::video::Window>, font_manager: &mut FontManager, layout: &TextLayout, progress: f32, theme: &ui::Theme) -> Result<(), String> {
+    font_manager.set_text_color(theme.text);
     font_manager.draw_layout(canvas, layout, 150, 150, false)?;
     let bar_bg_rect = Rect::new(100, 200, 312, 30);
-    canvas.set_draw_color(Color::RGB(80, 80, 80));
+    canvas.set_draw_color(theme.progress_empty);
     canvas.fill_rect(bar_bg_rect)?;
     let bar_width = (312.0 * progress) as u32;
     let bar_fg_rect = Rect::new(100, 200, bar_width.min(312), 30);
-    canvas.set_draw_color(Color::RGB(100, 180, 255));
+    canvas.set_draw_color(theme.progress_full_high);
     canvas.fill_rect(bar_fg_rect)?;
     Ok(())
 }
 
-fn draw_error_scene(canvas: &mut sdl2::render::Canvas<sdl2::video::Window>, font_manager: &mut FontManager, msg: &str) -> Result<(), String> {
+fn draw_error_scene(canvas: &mut sdl2::render::Canvas<sdl2::video::Window>, font_manager: &mut FontManager, msg: &str, translations: &Translations, theme: &ui::Theme) -> Result<(), String> {
+    font_manager.set_text_color(theme.text);
     let margin: u32 = 30;
-    let error_spans = html_parser::parse_html_to_spans(&format!("Error: {}", msg));
+    let error_text = translations.tr_with(i18n::StringId::ErrorPrefix, &[("message", msg)]);
+    let error_spans = html_parser::parse_html_to_spans(&error_text);
     let layout = font_manager.layout_text_binary(&error_spans, 512 - margin * 2, false)?;
     font_manager.draw_layout(canvas, &layout, margin as i32, 40, false)?;
     Ok(())
@@ -0,0 +1,243 @@
+// src/menu.rs
+// A reusable vertical list-navigation widget. `handle_main_menu_input` used
+// to hardcode its own up/down/A logic and option list; pulling that into
+// `Menu` lets later screens (settings, controls, profile) reuse the same
+// navigation instead of copying it again.
+
+use crate::state::{BrickButton, BrickInput};
+
+/// Ticks (simulation steps, per `update_state`) a D-pad direction must stay
+/// held before auto-repeat kicks in.
+const REPEAT_DELAY_TICKS: u32 = 18;
+/// Ticks between each auto-repeated move once a direction has been held past
+/// `REPEAT_DELAY_TICKS`.
+const REPEAT_INTERVAL_TICKS: u32 = 5;
+
+/// A single row in a `Menu`.
+pub enum MenuEntry {
+    /// A normal selectable row that reports `Selected` when chosen.
+    Active(String),
+    /// An unselectable row (e.g. a section title), skipped by up/down.
+    Disabled(String),
+    /// A selectable row holding its own on/off state, flipped in place by A
+    /// rather than reporting a plain `Selected`.
+    Toggle(String, bool),
+}
+
+impl MenuEntry {
+    pub fn label(&self) -> &str {
+        match self {
+            MenuEntry::Active(label) => label,
+            MenuEntry::Disabled(label) => label,
+            MenuEntry::Toggle(label, _) => label,
+        }
+    }
+
+    fn is_selectable(&self) -> bool {
+        !matches!(self, MenuEntry::Disabled(_))
+    }
+}
+
+/// What happened to a `Menu` as a result of `process_input`.
+pub enum MenuSelectionResult<'a> {
+    /// Navigation, or an input the menu doesn't act on.
+    None,
+    /// The menu should be left (e.g. `BrickButton::Back`).
+    Canceled,
+    /// A selectable row was chosen with A. `Toggle` rows have already
+    /// flipped their bool by the time this is returned.
+    Selected(usize, &'a mut MenuEntry),
+}
+
+/// Owns a list of rows and a cursor, and turns `BrickInput` into movement,
+/// toggling, or selection. Disabled rows are skipped by up/down and can't be
+/// selected.
+pub struct Menu {
+    pub entries: Vec<MenuEntry>,
+    pub selected: usize,
+    /// Direction (-1 up, 1 down) currently held by the D-pad, for auto-repeat
+    /// via `tick`. `None` while idle or right after a release.
+    held_direction: Option<isize>,
+    /// Ticks `held_direction` has been held for, reset on every press.
+    held_ticks: u32,
+}
+
+impl Menu {
+    pub fn new(entries: Vec<MenuEntry>) -> Self {
+        let selected = entries.iter().position(|e| e.is_selectable()).unwrap_or(0);
+        Self { entries, selected, held_direction: None, held_ticks: 0 }
+    }
+
+    /// Moves the cursor by `delta` rows, wrapping from the last selectable
+    /// row back to the first (and vice versa) and skipping over any
+    /// `Disabled` rows it lands on along the way.
+    fn move_selection(&mut self, delta: isize) {
+        if self.entries.is_empty() {
+            return;
+        }
+        let len = self.entries.len() as isize;
+        let mut idx = self.selected as isize;
+        for _ in 0..len {
+            idx = (idx + delta).rem_euclid(len);
+            if self.entries[idx as usize].is_selectable() {
+                self.selected = idx as usize;
+                return;
+            }
+        }
+    }
+
+    /// Translates one `BrickInput` into navigation/toggling/selection. A
+    /// D-pad `ButtonDown` also (re)starts the hold timer `tick` uses for
+    /// auto-repeat; the matching `ButtonUp` stops it.
+    pub fn process_input(&mut self, input: BrickInput) -> MenuSelectionResult {
+        match input {
+            BrickInput::ButtonDown(BrickButton::DPadDown) => {
+                self.move_selection(1);
+                self.held_direction = Some(1);
+                self.held_ticks = 0;
+                MenuSelectionResult::None
+            }
+            BrickInput::ButtonDown(BrickButton::DPadUp) => {
+                self.move_selection(-1);
+                self.held_direction = Some(-1);
+                self.held_ticks = 0;
+                MenuSelectionResult::None
+            }
+            BrickInput::ButtonUp(BrickButton::DPadDown) | BrickInput::ButtonUp(BrickButton::DPadUp) => {
+                self.held_direction = None;
+                MenuSelectionResult::None
+            }
+            BrickInput::ButtonDown(BrickButton::A) => {
+                let index = self.selected;
+                match self.entries.get_mut(index) {
+                    Some(entry) if entry.is_selectable() => {
+                        if let MenuEntry::Toggle(_, value) = entry {
+                            *value = !*value;
+                        }
+                        MenuSelectionResult::Selected(index, entry)
+                    }
+                    _ => MenuSelectionResult::None,
+                }
+            }
+            BrickInput::ButtonDown(BrickButton::Back) => MenuSelectionResult::Canceled,
+            _ => MenuSelectionResult::None,
+        }
+    }
+
+    /// Advances the held-direction timer by one simulation step, firing an
+    /// additional move once it's been held past `REPEAT_DELAY_TICKS` and
+    /// every `REPEAT_INTERVAL_TICKS` after that. Returns whether a move
+    /// fired, so the caller knows whether to play its move sound. Called
+    /// once per tick from `update_state` rather than per event, since a held
+    /// button doesn't generate its own repeated `ButtonDown`s.
+    pub fn tick(&mut self) -> bool {
+        let Some(direction) = self.held_direction else { return false };
+        self.held_ticks += 1;
+        if self.held_ticks < REPEAT_DELAY_TICKS {
+            return false;
+        }
+        if (self.held_ticks - REPEAT_DELAY_TICKS) % REPEAT_INTERVAL_TICKS == 0 {
+            self.move_selection(direction);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn active(label: &str) -> MenuEntry {
+        MenuEntry::Active(label.to_string())
+    }
+
+    #[test]
+    fn move_selection_wraps_from_last_row_to_first_and_back() {
+        let mut menu = Menu::new(vec![active("a"), active("b"), active("c")]);
+        menu.selected = 2;
+
+        menu.process_input(BrickInput::ButtonDown(BrickButton::DPadDown));
+        assert_eq!(menu.selected, 0);
+
+        menu.process_input(BrickInput::ButtonDown(BrickButton::DPadUp));
+        assert_eq!(menu.selected, 2);
+    }
+
+    #[test]
+    fn move_selection_skips_over_disabled_rows() {
+        let mut menu = Menu::new(vec![
+            MenuEntry::Disabled("header".to_string()),
+            active("a"),
+            MenuEntry::Disabled("section".to_string()),
+            active("b"),
+        ]);
+        menu.selected = 1; // "a"
+
+        // Moving down lands on the disabled row first; it should keep
+        // stepping until it finds the next selectable one.
+        menu.process_input(BrickInput::ButtonDown(BrickButton::DPadDown));
+        assert_eq!(menu.selected, 3); // "b"
+
+        // Moving up from "b" steps back past the same disabled row.
+        menu.process_input(BrickInput::ButtonDown(BrickButton::DPadUp));
+        assert_eq!(menu.selected, 1); // "a"
+    }
+
+    #[test]
+    fn move_selection_on_an_all_disabled_menu_does_not_panic_or_move() {
+        let mut menu = Menu::new(vec![
+            MenuEntry::Disabled("a".to_string()),
+            MenuEntry::Disabled("b".to_string()),
+        ]);
+        assert_eq!(menu.selected, 0);
+
+        menu.process_input(BrickInput::ButtonDown(BrickButton::DPadDown));
+        assert_eq!(menu.selected, 0);
+    }
+
+    #[test]
+    fn move_selection_on_an_empty_menu_does_not_panic() {
+        let mut menu = Menu::new(vec![]);
+        assert_eq!(menu.selected, 0);
+
+        menu.process_input(BrickInput::ButtonDown(BrickButton::DPadDown));
+        assert_eq!(menu.selected, 0);
+    }
+
+    #[test]
+    fn tick_repeats_only_after_the_delay_then_on_every_interval() {
+        let mut menu = Menu::new(vec![active("a"), active("b"), active("c")]);
+        menu.process_input(BrickInput::ButtonDown(BrickButton::DPadDown));
+        assert_eq!(menu.selected, 1);
+
+        // Held ticks 1..=(REPEAT_DELAY_TICKS - 1) shouldn't fire yet.
+        for _ in 0..REPEAT_DELAY_TICKS - 1 {
+            assert!(!menu.tick());
+        }
+        assert_eq!(menu.selected, 1);
+
+        // The tick landing exactly on REPEAT_DELAY_TICKS fires the first repeat.
+        assert!(menu.tick());
+        assert_eq!(menu.selected, 2);
+
+        // Nothing fires again until REPEAT_INTERVAL_TICKS later.
+        for _ in 0..REPEAT_INTERVAL_TICKS - 1 {
+            assert!(!menu.tick());
+        }
+        assert!(menu.tick());
+        assert_eq!(menu.selected, 0);
+    }
+
+    #[test]
+    fn tick_does_nothing_once_the_direction_is_released() {
+        let mut menu = Menu::new(vec![active("a"), active("b")]);
+        menu.process_input(BrickInput::ButtonDown(BrickButton::DPadDown));
+        menu.process_input(BrickInput::ButtonUp(BrickButton::DPadDown));
+
+        for _ in 0..REPEAT_DELAY_TICKS + REPEAT_INTERVAL_TICKS {
+            assert!(!menu.tick());
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+
+use crate::menu::MenuSelectionResult;
+use crate::scenes::controls_menu::conflict_message;
+use crate::scenes::main_menu::MainMenuState;
+use crate::sound::Sfx;
+use crate::state::{map_to_brick_input, BrickInput, BrickButton};
+use crate::{AppState, GameState};
+
+use super::REBINDABLE_BUTTONS;
+
+pub fn handle_controls_menu_input(state: &mut AppState, event: Event) -> Result<(), String> {
+    // While waiting for a key to bind, every `KeyDown` is consumed here
+    // rather than going through `Menu::process_input`, so the capture step
+    // can't be accidentally navigated away from.
+    let capturing = if let GameState::ControlsMenu(controls_menu) = &state.game_state {
+        controls_menu.capturing
+    } else {
+        return Ok(());
+    };
+
+    if let Some(row) = capturing {
+        if let Event::KeyDown { keycode: Some(keycode), repeat: false, .. } = event {
+            if keycode == Keycode::Escape {
+                if let GameState::ControlsMenu(controls_menu) = &mut state.game_state {
+                    controls_menu.capturing = None;
+                }
+            } else {
+                let button = REBINDABLE_BUTTONS[row];
+                match state.config.key_bindings.rebind_key(button, keycode) {
+                    Ok(()) => {
+                        state.config.key_bindings.save(&state.config.keybindings_path).ok();
+                        if let GameState::ControlsMenu(controls_menu) = &mut state.game_state {
+                            controls_menu.refresh_labels(&state.config.key_bindings);
+                            controls_menu.capturing = None;
+                            controls_menu.conflict_message = None;
+                        }
+                    }
+                    Err(conflict) => {
+                        if let GameState::ControlsMenu(controls_menu) = &mut state.game_state {
+                            controls_menu.capturing = None;
+                            controls_menu.conflict_message =
+                                Some(conflict_message(conflict, &state.translations));
+                        }
+                    }
+                }
+            }
+        }
+        return Ok(());
+    }
+
+    if let GameState::ControlsMenu(controls_menu) = &mut state.game_state {
+        if let Some(input) = map_to_brick_input(&event, &mut state.config.key_bindings) {
+            let moved = matches!(
+                input,
+                BrickInput::ButtonDown(BrickButton::DPadDown) | BrickInput::ButtonDown(BrickButton::DPadUp)
+            );
+            match controls_menu.menu.process_input(input) {
+                MenuSelectionResult::Selected(index, _) => {
+                    controls_menu.capturing = Some(index);
+                    controls_menu.conflict_message = None;
+                }
+                MenuSelectionResult::Canceled => {
+                    state.game_state = GameState::MainMenu(MainMenuState::new(&state.translations));
+                    return Ok(());
+                }
+                MenuSelectionResult::None => {}
+            }
+            if moved {
+                state.sound.play_sfx(Sfx::UpDown);
+            }
+        } else if let Event::KeyDown { keycode: Some(keycode), repeat: false, .. } = event {
+            match keycode {
+                Keycode::Up => {
+                    controls_menu.menu.process_input(BrickInput::ButtonDown(BrickButton::DPadUp));
+                    state.sound.play_sfx(Sfx::UpDown);
+                }
+                Keycode::Down => {
+                    controls_menu.menu.process_input(BrickInput::ButtonDown(BrickButton::DPadDown));
+                    state.sound.play_sfx(Sfx::UpDown);
+                }
+                Keycode::Return => {
+                    if let MenuSelectionResult::Selected(index, _) =
+                        controls_menu.menu.process_input(BrickInput::ButtonDown(BrickButton::A))
+                    {
+                        controls_menu.capturing = Some(index);
+                        controls_menu.conflict_message = None;
+                    }
+                }
+                Keycode::Escape | Keycode::Backspace => {
+                    state.game_state = GameState::MainMenu(MainMenuState::new(&state.translations));
+                }
+                _ => {}
+            }
+        } else if let Event::KeyUp { keycode: Some(key), .. } = event {
+            // Stops `Menu::tick`'s auto-repeat once an arrow key typed
+            // outside the binding table (see the `KeyDown` arm above) is
+            // released.
+            match key {
+                Keycode::Up => { controls_menu.menu.process_input(BrickInput::ButtonUp(BrickButton::DPadUp)); }
+                Keycode::Down => { controls_menu.menu.process_input(BrickInput::ButtonUp(BrickButton::DPadDown)); }
+                _ => {}
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,114 @@
+// src/scenes/controls_menu/mod.rs
+
+use sdl2::rect::Rect;
+use sdl2::render::Canvas;
+use sdl2::video::Window;
+
+use crate::i18n::{StringId, Translations};
+use crate::menu::{Menu, MenuEntry};
+use crate::state::{BrickButton, KeyBindings, RebindConflict};
+use crate::ui::{FontManager, Theme};
+
+pub mod input;
+
+/// Every `BrickButton` the controls menu lets a player rebind to a keyboard
+/// key, in the order they're listed. Matches `ControlsMenuState::menu`'s
+/// entries 1:1 by index.
+pub const REBINDABLE_BUTTONS: [BrickButton; 10] = [
+    BrickButton::DPadUp,
+    BrickButton::DPadDown,
+    BrickButton::DPadLeft,
+    BrickButton::DPadRight,
+    BrickButton::A,
+    BrickButton::B,
+    BrickButton::X,
+    BrickButton::Y,
+    BrickButton::LeftShoulder,
+    BrickButton::RightShoulder,
+];
+
+/// Contains the state specific to the controls (key-rebinding) screen.
+pub struct ControlsMenuState {
+    pub menu: Menu,
+    /// `Some(index into REBINDABLE_BUTTONS)` once A is pressed on that row,
+    /// waiting for the next `KeyDown` to bind. Escape cancels it without
+    /// changing the binding.
+    pub capturing: Option<usize>,
+    /// Set when the last capture attempt was refused by `rebind_key`, so the
+    /// player sees why nothing changed instead of the key silently not
+    /// taking. Cleared as soon as a new capture starts.
+    pub conflict_message: Option<String>,
+}
+
+impl ControlsMenuState {
+    pub fn new(bindings: &KeyBindings) -> Self {
+        let entries = REBINDABLE_BUTTONS
+            .iter()
+            .map(|button| MenuEntry::Active(row_label(*button, bindings)))
+            .collect();
+        Self { menu: Menu::new(entries), capturing: None, conflict_message: None }
+    }
+
+    /// Rebuilds every row's label from `bindings`, after a rebind changes one.
+    pub fn refresh_labels(&mut self, bindings: &KeyBindings) {
+        for (entry, button) in self.menu.entries.iter_mut().zip(REBINDABLE_BUTTONS.iter()) {
+            *entry = MenuEntry::Active(row_label(*button, bindings));
+        }
+    }
+}
+
+/// Formats one row as `"<action>: <key>"`, or `"(unbound)"` if `button` has
+/// no keycode of its own yet (it may still work via a controller/joystick
+/// binding `KeyBindings` doesn't show here).
+fn row_label(button: BrickButton, bindings: &KeyBindings) -> String {
+    match bindings.keycode_for(button) {
+        Some(keycode) => format!("{}: {}", button.label(), keycode.name()),
+        None => format!("{}: (unbound)", button.label()),
+    }
+}
+
+/// Explains a refused capture for `conflict_message`.
+pub fn conflict_message(conflict: RebindConflict, translations: &Translations) -> String {
+    match conflict {
+        RebindConflict::Reserved => translations.tr(StringId::ConflictReserved).to_string(),
+        RebindConflict::AlreadyBound(other) => {
+            translations.tr_with(StringId::ConflictAlreadyBound, &[("action", other.label())])
+        }
+    }
+}
+
+/// Draws the controls scene: a D-pad-navigable list of rebindable actions,
+/// styled the same way `options` draws its rows. While `capturing` is set,
+/// the selected row's hint line prompts for the next key press instead.
+pub fn draw_controls_menu_scene(
+    canvas: &mut Canvas<Window>,
+    font_manager: &mut FontManager,
+    state: &ControlsMenuState,
+    translations: &Translations,
+    theme: &Theme,
+) -> Result<(), String> {
+    font_manager.set_text_color(theme.text);
+    font_manager.draw_single_line(canvas, translations.tr(StringId::ControlsTitle), 20, 20)?;
+
+    let mut y_pos = 80;
+    for (i, entry) in state.menu.entries.iter().enumerate() {
+        let label = entry.label();
+        if i == state.menu.selected {
+            let (text_w, text_h) = font_manager.size_of_text(label)?;
+            let highlight_rect = Rect::new(18, y_pos - 2, text_w + 8, text_h + 4);
+            canvas.set_draw_color(theme.menu_highlight);
+            canvas.fill_rect(highlight_rect)?;
+            font_manager.set_text_color(theme.text);
+        }
+        font_manager.draw_single_line(canvas, label, 20, y_pos)?;
+        y_pos += 40;
+    }
+
+    if state.capturing.is_some() {
+        font_manager.draw_single_line(canvas, "Press a key... (Escape to cancel)", 20, y_pos + 20)?;
+    } else if let Some(message) = &state.conflict_message {
+        font_manager.draw_single_line(canvas, message, 20, y_pos + 20)?;
+    }
+
+    Ok(())
+}
@@ -1,4 +1,3 @@
-use crate::mixer::Channel;
 use std::sync::mpsc;
 use std::thread;
 
@@ -6,45 +5,65 @@ use sdl2::event::Event;
 use sdl2::keyboard::Keycode;
 
 use crate::deck::html_parser;
+use crate::i18n::StringId;
 use crate::scenes::main_menu::MainMenuState;
+use crate::sound::Sfx;
 use crate::{AppState, GameState};
-use crate::state::{map_to_brick_input, BrickInput, BrickButton};
+use crate::state::{map_to_brick_input, BrickAxis, BrickInput, BrickButton};
 
 /// Handles input events for the deck selection scene.
 pub fn handle_deck_selection_input(state: &mut AppState, event: Event) -> Result<(), String> {
-    if let Some(input) = map_to_brick_input(&event) {
+    if let Some(input) = map_to_brick_input(&event, &mut state.config.key_bindings) {
         if let GameState::DeckSelection(deck_selection_state) = &mut state.game_state {
 
             match input {
                 BrickInput::ButtonDown(BrickButton::DPadUp) => {
-                    deck_selection_state.selected_index = deck_selection_state.selected_index.saturating_sub(1);
-                    Channel::all().play(&state.sfx.up_down_sound, 0)?;
+                    deck_selection_state.set_dpad_held(Some(-1));
+                    if !deck_selection_state.filtered.is_empty() {
+                        deck_selection_state.move_selection(-1, deck_selection_state.filtered.len(), 3);
+                        state.sound.play_sfx(Sfx::UpDown);
+                    }
                 },
                 BrickInput::ButtonDown(BrickButton::DPadDown) => {
-                    // Ensure we don't go out of bounds if there are decks.
-                    if !deck_selection_state.decks.is_empty() {
-                        Channel::all().play(&state.sfx.up_down_sound, 0)?;
-                        deck_selection_state.selected_index = (deck_selection_state.selected_index + 1).min(deck_selection_state.decks.len() - 1);
+                    // Ensure we don't go out of bounds if there are matches.
+                    deck_selection_state.set_dpad_held(Some(1));
+                    if !deck_selection_state.filtered.is_empty() {
+                        state.sound.play_sfx(Sfx::UpDown);
+                        deck_selection_state.move_selection(1, deck_selection_state.filtered.len(), 3);
                     }
                 }
+                BrickInput::ButtonUp(BrickButton::DPadUp) | BrickInput::ButtonUp(BrickButton::DPadDown) => {
+                    deck_selection_state.set_dpad_held(None);
+                }
                 BrickInput::ButtonDown(BrickButton::A) => {
-                    if !deck_selection_state.decks.is_empty() {
-                        let selected_deck = &deck_selection_state.decks[deck_selection_state.selected_index];
+                    if let Some(deck_idx) = deck_selection_state.selected_deck_index() {
+                        let selected_deck = &deck_selection_state.decks[deck_idx];
                         let deck_path = selected_deck.path.clone();
                         let deck_id = selected_deck.id.clone();
                         let (tx, rx) = mpsc::channel();
-                        thread::spawn(move || { crate::deck::loader::load_apkg(&deck_path, tx); });
-                        let loading_spans = html_parser::parse_html_to_spans("Loading Deck...");
+                        let loader_deck_path = deck_path.clone();
+                        let loader_deck_id = deck_id.clone();
+                        thread::spawn(move || { crate::deck::loader::load_apkg(&loader_deck_path, &loader_deck_id, tx); });
+                        let loading_spans = html_parser::parse_html_to_spans(state.translations.tr(StringId::LoadingDeck));
                         let loading_layout = state.font_manager.layout_text_binary(&loading_spans, 400, false)?;
-                        Channel::all().play(&state.sfx.open_sound, 0)?;
-                        state.game_state = GameState::Loading { rx, loading_layout, progress: 0.0, deck_id_to_load: deck_id };
+                        state.sound.play_sfx(Sfx::Open);
+                        state.game_state = GameState::Loading { rx, loading_layout, progress: 0.0, deck_id_to_load: deck_id, deck_path };
                     }
                 },
-                BrickInput::ButtonDown(BrickButton::Back) => state.game_state = GameState::MainMenu(MainMenuState::new()),
+                BrickInput::ButtonDown(BrickButton::Back) => state.game_state = GameState::MainMenu(MainMenuState::new(&state.translations)),
+
+                BrickInput::AxisMotion { axis: BrickAxis::TriggerLeft, value } => deck_selection_state.trigger_left = value,
+                BrickInput::AxisMotion { axis: BrickAxis::TriggerRight, value } => deck_selection_state.trigger_right = value,
 
                 _ => {}
             }
         }
+    } else if let Event::TextInput { text, .. } = &event {
+        if let GameState::DeckSelection(deck_selection_state) = &mut state.game_state {
+            for ch in text.chars() {
+                deck_selection_state.push_query_char(ch);
+            }
+        }
     } else {
 
         if let Event::KeyDown { keycode: Some(keycode), repeat: false, .. } = event {
@@ -52,39 +71,50 @@ pub fn handle_deck_selection_input(state: &mut AppState, event: Event) -> Result
 
                 match keycode {
                     Keycode::Up => {
-                        // deck_selection_state.selected_index = deck_selection_state.selected_index.saturating_sub(1);
-                        deck_selection_state.move_selection(-1, deck_selection_state.decks.len(), 3);
-                        Channel::all().play(&state.sfx.up_down_sound, 0)?;
-
+                        deck_selection_state.set_dpad_held(Some(-1));
+                        if !deck_selection_state.filtered.is_empty() {
+                            deck_selection_state.move_selection(-1, deck_selection_state.filtered.len(), 3);
+                            state.sound.play_sfx(Sfx::UpDown);
+                        }
                     }
                     Keycode::Down => {
-                        // Ensure we don't go out of bounds if there are decks.
-                        if !deck_selection_state.decks.is_empty() {
-                            // deck_selection_state.selected_index = (deck_selection_state.selected_index + 1).min(deck_selection_state.decks.len() - 1);
-                            deck_selection_state.move_selection( 1, deck_selection_state.decks.len(), 3);
-                            Channel::all().play(&state.sfx.up_down_sound, 0)?;
+                        // Ensure we don't go out of bounds if there are matches.
+                        deck_selection_state.set_dpad_held(Some(1));
+                        if !deck_selection_state.filtered.is_empty() {
+                            deck_selection_state.move_selection( 1, deck_selection_state.filtered.len(), 3);
+                            state.sound.play_sfx(Sfx::UpDown);
                         }
                     }
                     Keycode::Backspace => {
-                        state.game_state = GameState::MainMenu(MainMenuState::new());
+                        // Editing the search query takes priority over leaving the
+                        // scene, so typing a typo doesn't accidentally bail out.
+                        if !deck_selection_state.pop_query_char() {
+                            state.game_state = GameState::MainMenu(MainMenuState::new(&state.translations));
+                        }
                     }
                     Keycode::Return => {
-                        // Guard against crashing if Enter is pressed when the deck list is empty.
-                        if !deck_selection_state.decks.is_empty() {
-                            let selected_deck = &deck_selection_state.decks[deck_selection_state.selected_index];
+                        // Guard against crashing if Enter is pressed when there are no matches.
+                        if let Some(deck_idx) = deck_selection_state.selected_deck_index() {
+                            let selected_deck = &deck_selection_state.decks[deck_idx];
                             let deck_path = selected_deck.path.clone();
                             let deck_id = selected_deck.id.clone();
                             let (tx, rx) = mpsc::channel();
-                            thread::spawn(move || { crate::deck::loader::load_apkg(&deck_path, tx); });
-                            let loading_spans = html_parser::parse_html_to_spans("Loading Deck...");
+                            let loader_deck_path = deck_path.clone();
+                            let loader_deck_id = deck_id.clone();
+                            thread::spawn(move || { crate::deck::loader::load_apkg(&loader_deck_path, &loader_deck_id, tx); });
+                            let loading_spans = html_parser::parse_html_to_spans(state.translations.tr(StringId::LoadingDeck));
                             let loading_layout = state.font_manager.layout_text_binary(&loading_spans, 400, false)?;
-                            Channel::all().play(&state.sfx.open_sound, 0)?;
-                            state.game_state = GameState::Loading { rx, loading_layout, progress: 0.0, deck_id_to_load: deck_id };
+                            state.sound.play_sfx(Sfx::Open);
+                            state.game_state = GameState::Loading { rx, loading_layout, progress: 0.0, deck_id_to_load: deck_id, deck_path };
                         }
                     }
                     _ => {}
                 }
             }
+        } else if let Event::KeyUp { keycode: Some(Keycode::Up) | Some(Keycode::Down), .. } = event {
+            if let GameState::DeckSelection(deck_selection_state) = &mut state.game_state {
+                deck_selection_state.set_dpad_held(None);
+            }
         }
     }
     Ok(())
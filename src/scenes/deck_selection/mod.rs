@@ -1,12 +1,12 @@
 use sdl2::surface::Surface;
-use sdl2::pixels::Color;
 use sdl2::rect::Rect;
 use sdl2::render::Canvas;
 use sdl2::video::Window;
 
 use crate::config::Config;
+use crate::i18n::{StringId, Translations};
 use crate::DeckMetadata;
-use crate::ui::FontManager;
+use crate::ui::{FontManager, Theme};
 
 pub mod input;
 
@@ -19,10 +19,45 @@ pub struct DeckRenderInfo {
 pub struct DeckSelectionState {
     pub decks: Vec<DeckMetadata>,
     pub rendered_decks: Vec<DeckRenderInfo>,
+    /// Position within `filtered`, not within `decks`.
     pub selected_index: usize,
     first_visible: usize,
+    /// Current incremental-search text, typed via `TextInput` events.
+    pub query: String,
+    /// Indices into `decks`/`rendered_decks` that match `query`, sorted by
+    /// descending fuzzy-match score. Equal to `0..decks.len()` when `query`
+    /// is empty.
+    pub filtered: Vec<usize>,
+    /// Latest `BrickAxis::TriggerLeft`/`TriggerRight` values (0.0 when idle
+    /// or released), updated on `AxisMotion` events and consumed once per
+    /// frame by `apply_axis_scroll`.
+    pub trigger_left: f32,
+    pub trigger_right: f32,
+    /// Fractional rows accumulated by analog scrolling since the last whole
+    /// row was applied via `move_selection`.
+    axis_scroll_accum: f32,
+    /// Direction (-1 up, 1 down) currently held by the D-pad, for
+    /// `apply_dpad_repeat`'s auto-repeat. `None` while idle.
+    dpad_held_direction: Option<isize>,
+    /// Ticks `dpad_held_direction` has been held for, reset on every press.
+    dpad_held_ticks: u32,
 }
 
+/// Ticks a held D-pad direction must stay pressed before auto-repeat kicks
+/// in, and the ticks between each repeated move after that. Mirrors
+/// `Menu::tick`'s constants; this scene predates `Menu` and scrolls a
+/// windowed list rather than a flat entry list, so it repeats the pattern
+/// locally instead of sharing the widget.
+const DPAD_REPEAT_DELAY_TICKS: u32 = 18;
+const DPAD_REPEAT_INTERVAL_TICKS: u32 = 5;
+
+/// Below this magnitude a trigger is treated as released, so stick drift and
+/// resting contact noise don't cause unwanted scrolling.
+const AXIS_DEADZONE: f32 = 0.12;
+/// Rows per frame scrolled at full trigger deflection, before the quadratic
+/// acceleration curve is applied.
+const AXIS_SCROLL_ROWS_PER_FRAME: f32 = 0.5;
+
 
 impl DeckSelectionState {
     /// This is where the caching happens. Call this once when switching to this scene.
@@ -32,11 +67,16 @@ impl DeckSelectionState {
         config: &Config,
     ) -> Result<Self, String> {
         let mut rendered_decks = Vec::new();
-        let max_width = config.logical_window_width - 80;
+        let max_width = config.profile.logical_width - config.profile.selection_margin;
 
         for deck in &decks {
-            let display_title = deck.name.replace('_', " ");
-            
+            let display_title = format!(
+                "{}  ({} due, {} new)",
+                deck.name.replace('_', " "),
+                deck.due_count,
+                deck.new_count,
+            );
+
             // Perform the expensive rendering operation here.
             let (surface, width, height) = small_font_manager.render_text_to_surface(
                 &display_title,
@@ -44,16 +84,26 @@ impl DeckSelectionState {
                 80, // box_height
                 10, // min_pt
                 32, // max_pt
+                config.theme.text,
             )?;
 
             rendered_decks.push(DeckRenderInfo { surface, width, height });
         }
 
+        let filtered = (0..decks.len()).collect();
+
         Ok(DeckSelectionState {
             decks,
             rendered_decks,
             selected_index: 0,
             first_visible: 0,
+            query: String::new(),
+            filtered,
+            trigger_left: 0.0,
+            trigger_right: 0.0,
+            axis_scroll_accum: 0.0,
+            dpad_held_direction: None,
+            dpad_held_ticks: 0,
         })
     }
 
@@ -69,6 +119,152 @@ impl DeckSelectionState {
             self.first_visible = self.selected_index - visible + 1;
         }
     }
+
+    /// Records which direction (if any) the D-pad is currently held in, and
+    /// resets the hold timer. Called from `handle_deck_selection_input` on
+    /// every `DPadUp`/`DPadDown` press or release.
+    pub fn set_dpad_held(&mut self, direction: Option<isize>) {
+        self.dpad_held_direction = direction;
+        self.dpad_held_ticks = 0;
+    }
+
+    /// Mirrors `Menu::tick`'s auto-repeat for this scene's own up/down
+    /// scroller, since `DeckSelectionState` doesn't use the `Menu` widget.
+    /// Returns whether a repeat move fired, so the caller knows whether to
+    /// play its move sound.
+    pub fn apply_dpad_repeat(&mut self) -> bool {
+        let Some(direction) = self.dpad_held_direction else { return false };
+        if self.filtered.is_empty() {
+            return false;
+        }
+        self.dpad_held_ticks += 1;
+        if self.dpad_held_ticks < DPAD_REPEAT_DELAY_TICKS {
+            return false;
+        }
+        if (self.dpad_held_ticks - DPAD_REPEAT_DELAY_TICKS) % DPAD_REPEAT_INTERVAL_TICKS == 0 {
+            self.move_selection(direction, self.filtered.len(), 3);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Consumes the latest `trigger_left`/`trigger_right` values to scroll
+    /// the list at a rate proportional to (and accelerating with) how far a
+    /// trigger is held. Called once per frame rather than on event edges, so
+    /// holding a trigger keeps scrolling without repeated presses. Snaps back
+    /// to whole-row steps the moment both triggers fall back into the
+    /// deadzone, so a released trigger never leaves a half-applied row.
+    pub fn apply_axis_scroll(&mut self) {
+        let net = self.trigger_right - self.trigger_left;
+        if net.abs() <= AXIS_DEADZONE || self.filtered.is_empty() {
+            self.axis_scroll_accum = 0.0;
+            return;
+        }
+
+        let rate = net.signum() * net.abs().powi(2) * AXIS_SCROLL_ROWS_PER_FRAME;
+        self.axis_scroll_accum += rate;
+
+        while self.axis_scroll_accum >= 1.0 {
+            self.move_selection(1, self.filtered.len(), 3);
+            self.axis_scroll_accum -= 1.0;
+        }
+        while self.axis_scroll_accum <= -1.0 {
+            self.move_selection(-1, self.filtered.len(), 3);
+            self.axis_scroll_accum += 1.0;
+        }
+    }
+
+    /// Appends a typed character to the search query and re-filters.
+    pub fn push_query_char(&mut self, ch: char) {
+        self.query.push(ch);
+        self.refilter();
+    }
+
+    /// Removes the last character of the search query, if any, and
+    /// re-filters. Returns `true` if a character was actually removed, so
+    /// callers can fall back to other Backspace behavior on an empty query.
+    pub fn pop_query_char(&mut self) -> bool {
+        let removed = self.query.pop().is_some();
+        if removed {
+            self.refilter();
+        }
+        removed
+    }
+
+    /// The deck (and its cached render) currently under the cursor, if the
+    /// filtered list isn't empty.
+    pub fn selected_deck_index(&self) -> Option<usize> {
+        self.filtered.get(self.selected_index).copied()
+    }
+
+    /// Recomputes `filtered` from `query` against `decks`, sorting hits by
+    /// descending fuzzy-match score, and snaps the cursor back to the top.
+    fn refilter(&mut self) {
+        if self.query.is_empty() {
+            self.filtered = (0..self.decks.len()).collect();
+        } else {
+            let mut scored: Vec<(usize, i32)> = self.decks.iter().enumerate()
+                .filter_map(|(i, deck)| fuzzy_score(&self.query, &deck.name).map(|score| (i, score)))
+                .collect();
+            scored.sort_by(|a, b| b.1.cmp(&a.1));
+            self.filtered = scored.into_iter().map(|(i, _)| i).collect();
+        }
+        self.selected_index = 0;
+        self.first_visible = 0;
+    }
+}
+
+/// Scores `candidate` as a case-insensitive fuzzy subsequence match against
+/// `query`, or returns `None` if `query` isn't a subsequence of `candidate`
+/// at all. Matches at the start of a word (index 0, or following `_`/space)
+/// score a bonus, consecutive matched characters build a streak bonus
+/// (`streak_length^2`), and skipping characters between matches costs points
+/// proportional to the gap.
+fn fuzzy_score(query: &str, candidate: &str) -> Option<i32> {
+    let query_chars: Vec<char> = query.to_lowercase().chars().collect();
+    let candidate_chars: Vec<char> = candidate.to_lowercase().chars().collect();
+    if query_chars.is_empty() {
+        return Some(0);
+    }
+
+    let mut score = 0i32;
+    let mut query_idx = 0usize;
+    let mut last_match_idx: Option<usize> = None;
+    let mut streak = 0i32;
+
+    for (candidate_idx, &c) in candidate_chars.iter().enumerate() {
+        if query_idx >= query_chars.len() {
+            break;
+        }
+        if c != query_chars[query_idx] {
+            continue;
+        }
+
+        let at_word_start = candidate_idx == 0
+            || candidate_chars[candidate_idx - 1] == '_'
+            || candidate_chars[candidate_idx - 1] == ' ';
+        if at_word_start {
+            score += 50;
+        }
+
+        match last_match_idx {
+            Some(last) if candidate_idx - last == 1 => {
+                streak += 1;
+                score += streak * streak;
+            }
+            Some(last) => {
+                streak = 0;
+                score -= (candidate_idx - last) as i32 * 2;
+            }
+            None => streak = 0,
+        }
+
+        last_match_idx = Some(candidate_idx);
+        query_idx += 1;
+    }
+
+    if query_idx == query_chars.len() { Some(score) } else { None }
 }
 
 
@@ -77,13 +273,27 @@ pub fn draw_deck_selection_scene(
     font_manager: &mut FontManager,
     small_font_manager: &mut FontManager,
     state: &DeckSelectionState,
+    translations: &Translations,
+    theme: &Theme,
 ) -> Result<(), String> {
-    font_manager.draw_single_line(canvas, "Select a Deck", 20, 20)?;
-    small_font_manager.draw_single_line(canvas, "Press Backspace to return to Main Menu", 20, 70)?;
+    font_manager.set_text_color(theme.text);
+    small_font_manager.set_text_color(theme.text);
+    font_manager.draw_single_line(canvas, translations.tr(StringId::SelectADeck), 20, 20)?;
+    small_font_manager.draw_single_line(canvas, translations.tr(StringId::ReturnToMainMenu), 20, 70)?;
+
+    if !state.query.is_empty() {
+        let search_text = format!("{}{}", translations.tr(StringId::SearchLabel), state.query);
+        small_font_manager.draw_single_line(canvas, &search_text, 20, 100)?;
+    }
 
     if state.decks.is_empty() {
-        small_font_manager.draw_single_line(canvas, "No decks found.", 20, 150)?;
-        small_font_manager.draw_single_line(canvas, "Please add .apkg files to the 'decks' directory.", 20, 180)?;
+        small_font_manager.draw_single_line(canvas, translations.tr(StringId::NoDecksFound), 20, 150)?;
+        small_font_manager.draw_single_line(canvas, translations.tr(StringId::NoDecksInstructions), 20, 180)?;
+        return Ok(());
+    }
+
+    if state.filtered.is_empty() {
+        small_font_manager.draw_single_line(canvas, translations.tr(StringId::NoMatchingDecks), 20, 150)?;
         return Ok(());
     }
 
@@ -93,13 +303,14 @@ pub fn draw_deck_selection_scene(
     let mut y_pos = list_top - 0;
 
     for row in 0..4 {
-        let idx = state.first_visible + row;
-        if idx >= state.rendered_decks.len() { break; }
+        let position = state.first_visible + row;
+        if position >= state.filtered.len() { break; }
+        let idx = state.filtered[position];
         let info = &state.rendered_decks[idx];
 
         // draw highlight on the *cursor* row
-        if idx == state.selected_index {
-            canvas.set_draw_color(Color::RGB(80, 80, 80));
+        if position == state.selected_index {
+            canvas.set_draw_color(theme.menu_highlight);
             let r = Rect::new(18, y_pos - 2, info.width + 4, info.height as u32 + 4);
             canvas.fill_rect(r)?;
         }
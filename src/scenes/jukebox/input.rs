@@ -0,0 +1,41 @@
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+
+use crate::scenes::main_menu::MainMenuState;
+use crate::sound::Sfx;
+use crate::state::{map_to_brick_input, BrickInput, BrickButton};
+use crate::{AppState, GameState};
+
+/// Handles input events for the jukebox (background-music selection) scene.
+pub fn handle_jukebox_input(state: &mut AppState, event: Event) -> Result<(), String> {
+    if let Some(input) = map_to_brick_input(&event, &mut state.config.key_bindings) {
+        if let GameState::Jukebox(jukebox_state) = &mut state.game_state {
+            match input {
+                BrickInput::ButtonDown(BrickButton::DPadUp) => {
+                    jukebox_state.move_selection(-1);
+                    state.sound.play_sfx(Sfx::UpDown);
+                }
+                BrickInput::ButtonDown(BrickButton::DPadDown) => {
+                    jukebox_state.move_selection(1);
+                    state.sound.play_sfx(Sfx::UpDown);
+                }
+                BrickInput::ButtonDown(BrickButton::A) => {
+                    if let Some(track) = jukebox_state.selected_track() {
+                        state.sound.play_bgm_file(&track.path, Some(track.name.clone()))?;
+                        state.sound.play_sfx(Sfx::Open);
+                    }
+                }
+                BrickInput::ButtonDown(BrickButton::B) => {
+                    state.sound.stop_bgm();
+                }
+                BrickInput::ButtonDown(BrickButton::Back) => {
+                    state.game_state = GameState::MainMenu(MainMenuState::new(&state.translations));
+                }
+                _ => {}
+            }
+        }
+    } else if let Event::KeyDown { keycode: Some(Keycode::Backspace), repeat: false, .. } = event {
+        state.game_state = GameState::MainMenu(MainMenuState::new(&state.translations));
+    }
+    Ok(())
+}
@@ -0,0 +1,118 @@
+// src/scenes/jukebox/mod.rs
+
+use std::fs;
+use std::path::PathBuf;
+
+use sdl2::rect::Rect;
+use sdl2::render::Canvas;
+use sdl2::video::Window;
+
+use crate::config::Config;
+use crate::i18n::{StringId, Translations};
+use crate::ui::{FontManager, Theme};
+
+pub mod input;
+
+/// File extensions SDL_mixer can load as background music.
+const MUSIC_EXTENSIONS: &[&str] = &["mp3", "ogg", "flac", "wav", "mod"];
+
+/// Metadata about a single track found in the `music/` directory.
+#[derive(Clone)]
+pub struct TrackMeta {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+/// Contains the state specific to the jukebox (background-music selection) screen.
+pub struct JukeboxState {
+    pub tracks: Vec<TrackMeta>,
+    pub selected_index: usize,
+}
+
+impl JukeboxState {
+    /// Scans `config.music_directory` for playable tracks. A missing or
+    /// unreadable directory just yields an empty list, the same way deck
+    /// selection shows "no decks found" instead of erroring.
+    pub fn new(config: &Config) -> Self {
+        let mut tracks = Vec::new();
+
+        if let Ok(entries) = fs::read_dir(&config.music_directory) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if !path.is_file() {
+                    continue;
+                }
+                let is_music = path
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .map(|ext| MUSIC_EXTENSIONS.iter().any(|known| known.eq_ignore_ascii_case(ext)))
+                    .unwrap_or(false);
+                if !is_music {
+                    continue;
+                }
+                let name = path
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .unwrap_or("unknown track")
+                    .replace('_', " ");
+                tracks.push(TrackMeta { name, path });
+            }
+        }
+        tracks.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Self { tracks, selected_index: 0 }
+    }
+
+    pub fn move_selection(&mut self, delta: isize) {
+        if self.tracks.is_empty() {
+            return;
+        }
+        let new_index = (self.selected_index as isize + delta)
+            .clamp(0, self.tracks.len() as isize - 1);
+        self.selected_index = new_index as usize;
+    }
+
+    pub fn selected_track(&self) -> Option<&TrackMeta> {
+        self.tracks.get(self.selected_index)
+    }
+}
+
+pub fn draw_jukebox_scene(
+    canvas: &mut Canvas<Window>,
+    font_manager: &mut FontManager,
+    small_font_manager: &mut FontManager,
+    state: &JukeboxState,
+    now_playing: Option<&str>,
+    translations: &Translations,
+    theme: &Theme,
+) -> Result<(), String> {
+    font_manager.set_text_color(theme.text);
+    small_font_manager.set_text_color(theme.text);
+    font_manager.draw_single_line(canvas, translations.tr(StringId::JukeboxTitle), 20, 20)?;
+    small_font_manager.draw_single_line(canvas, translations.tr(StringId::JukeboxHint), 20, 70)?;
+
+    if let Some(track_name) = now_playing {
+        let now_playing_text = format!("{}{}", translations.tr(StringId::NowPlaying), track_name);
+        small_font_manager.draw_single_line(canvas, &now_playing_text, 20, 100)?;
+    }
+
+    if state.tracks.is_empty() {
+        small_font_manager.draw_single_line(canvas, translations.tr(StringId::NoMusicFound), 20, 150)?;
+        small_font_manager.draw_single_line(canvas, translations.tr(StringId::NoMusicInstructions), 20, 180)?;
+        return Ok(());
+    }
+
+    let mut y_pos = 150;
+    for (i, track) in state.tracks.iter().enumerate() {
+        let (text_w, text_h) = small_font_manager.size_of_text(&track.name)?;
+        if i == state.selected_index {
+            canvas.set_draw_color(theme.menu_highlight);
+            let r = Rect::new(18, y_pos - 2, text_w + 4, text_h + 4);
+            canvas.fill_rect(r)?;
+        }
+        small_font_manager.draw_single_line(canvas, &track.name, 20, y_pos)?;
+        y_pos += text_h as i32 + 10;
+    }
+
+    Ok(())
+}
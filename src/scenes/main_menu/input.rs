@@ -1,69 +1,77 @@
-use crate::Channel;
 use sdl2::event::Event;
 use sdl2::keyboard::Keycode;
+use crate::menu::MenuSelectionResult;
+use crate::scenes::controls_menu::ControlsMenuState;
+use crate::scenes::settings::SettingsMenuState;
+use crate::sound::Sfx;
 use crate::state::{map_to_brick_input, BrickInput, BrickButton};
 
 use crate::{AppState, GameState};
 
-
 pub fn handle_main_menu_input(state: &mut AppState, event: Event) -> Result<(), String> {
+    let mut activated_index = None;
+    let mut play_move_sound = false;
+
     // Only run when we’re in the MainMenu state
     if let GameState::MainMenu(main_menu) = &mut state.game_state {
-        // Your three menu options
-        let options = ["Study", "Profile", "Quit"];
-
-        if let Some(input) = map_to_brick_input(&event) {
-            match input {
-                BrickInput::ButtonDown(BrickButton::DPadDown) => {
-                        main_menu.selected_index = (main_menu.selected_index + 1).min(options.len() - 1);
-                        Channel::all().play(&state.sfx.up_down_sound, 0)?;
+        if let Some(input) = map_to_brick_input(&event, &mut state.config.key_bindings) {
+            play_move_sound = matches!(
+                input,
+                BrickInput::ButtonDown(BrickButton::DPadDown) | BrickInput::ButtonDown(BrickButton::DPadUp)
+            );
+            if let MenuSelectionResult::Selected(index, _) = main_menu.menu.process_input(input) {
+                activated_index = Some(index);
+            }
+        } else if let Event::KeyDown { keycode: Some(key), repeat: false, .. } = event {
+            match key {
+                Keycode::Up => {
+                    main_menu.menu.process_input(BrickInput::ButtonDown(BrickButton::DPadUp));
+                    play_move_sound = true;
                 }
-                BrickInput::ButtonDown(BrickButton::DPadUp) => {
-                        main_menu.selected_index = main_menu.selected_index.saturating_sub(1);
-                        Channel::all().play(&state.sfx.up_down_sound, 0)?;
-                },
-                BrickInput::ButtonDown(BrickButton::A) => {
-                    Channel::all().play(&state.sfx.open_sound, 0)?;
-                    match main_menu.selected_index {
-                        0 => state.game_state = GameState::GoToDeckSelection,
-                        1 => { /* to Profile */ }
-                        2 => return Err("User quit".into()),
-                        _ => {}
+                Keycode::Down => {
+                    main_menu.menu.process_input(BrickInput::ButtonDown(BrickButton::DPadDown));
+                    play_move_sound = true;
+                }
+                Keycode::Return => {
+                    if let MenuSelectionResult::Selected(index, _) =
+                        main_menu.menu.process_input(BrickInput::ButtonDown(BrickButton::A))
+                    {
+                        activated_index = Some(index);
                     }
-                },
+                }
                 _ => {}
             }
-        } else {
-            match event {
-                // Keyboard
-                Event::KeyDown { keycode: Some(key), repeat: false, .. } => {
-                    match key {
-                        Keycode::Up   => {
-                            main_menu.selected_index = main_menu.selected_index.saturating_sub(1);
-                            Channel::all().play(&state.sfx.up_down_sound, 0)?;
-                        }
-                        Keycode::Down => {
-                            main_menu.selected_index = (main_menu.selected_index + 1).min(options.len() - 1);
-                            Channel::all().play(&state.sfx.up_down_sound, 0)?;
-
-                        }
-                        Keycode::Return => {
-                            Channel::all().play(&state.sfx.open_sound, 0)?;
-                            match main_menu.selected_index {
-                                0 => state.game_state = GameState::GoToDeckSelection,
-                                1 => { /* to Profile */ }
-                                2 => return Err("User quit".into()),
-                                _ => {}
-                            }
-                        }
-                        _ => {}
-                    }
-                }
-
+        } else if let Event::KeyUp { keycode: Some(key), .. } = event {
+            // Stops `Menu::tick`'s auto-repeat once an arrow key typed
+            // outside the binding table (see the `KeyDown` arm above) is
+            // released.
+            match key {
+                Keycode::Up => { main_menu.menu.process_input(BrickInput::ButtonUp(BrickButton::DPadUp)); }
+                Keycode::Down => { main_menu.menu.process_input(BrickInput::ButtonUp(BrickButton::DPadDown)); }
                 _ => {}
             }
+        }
+    }
 
+    if play_move_sound && state.settings.menu_sound_enabled {
+        state.sound.play_sfx(Sfx::UpDown);
+    }
 
+    if let Some(index) = activated_index {
+        if state.settings.menu_sound_enabled {
+            state.sound.play_sfx(Sfx::Open);
+        }
+        match index {
+            0 => state.game_state = GameState::GoToDeckSelection,
+            1 => { /* to Profile */ }
+            2 => state.game_state = GameState::GoToJukebox,
+            3 => state.game_state = GameState::ControlsMenu(ControlsMenuState::new(&state.config.key_bindings)),
+            4 => {
+                state.game_state =
+                    GameState::Settings(SettingsMenuState::new(&state.settings, &state.translations))
+            }
+            5 => return Err("User quit".into()),
+            _ => {}
         }
     }
 
@@ -1,11 +1,12 @@
 // src/scenes/main_menu/mod.rs
 
-use sdl2::pixels::Color;
 use sdl2::rect::Rect;
 use sdl2::render::Canvas;
 use sdl2::video::Window;
 
-use crate::ui::FontManager;
+use crate::i18n::{StringId, Translations};
+use crate::menu::{Menu, MenuEntry};
+use crate::ui::{FontManager, Theme};
 
 // This line was missing. It tells the main_menu module
 // that the input.rs file is part of it.
@@ -13,13 +14,39 @@ pub mod input;
 
 /// Contains the state specific to the main menu screen.
 pub struct MainMenuState {
-    pub selected_index: usize,
+    pub menu: Menu,
+    /// `Translations::version` at the time `menu`'s labels were last built,
+    /// so `refresh_if_stale` can tell a language switch happened elsewhere
+    /// and the cached labels need rebuilding.
+    built_for_version: u32,
 }
 
 impl MainMenuState {
     /// Creates a new MainMenuState with a default selection.
-    pub fn new() -> Self {
-        Self { selected_index: 0 }
+    pub fn new(translations: &Translations) -> Self {
+        Self { menu: Menu::new(Self::entries(translations)), built_for_version: translations.version() }
+    }
+
+    fn entries(translations: &Translations) -> Vec<MenuEntry> {
+        vec![
+            MenuEntry::Active(translations.tr(StringId::MenuStudy).to_string()),
+            MenuEntry::Active(translations.tr(StringId::MenuProfile).to_string()),
+            MenuEntry::Active(translations.tr(StringId::MenuMusic).to_string()),
+            MenuEntry::Active(translations.tr(StringId::MenuControls).to_string()),
+            MenuEntry::Active(translations.tr(StringId::MenuSettings).to_string()),
+            MenuEntry::Active(translations.tr(StringId::MenuQuit).to_string()),
+        ]
+    }
+
+    /// Rebuilds the menu's labels if the active language changed since they
+    /// were last built, so switching languages from the settings screen
+    /// doesn't leave this menu showing stale text if it's ever resumed
+    /// in place rather than freshly constructed.
+    pub fn refresh_if_stale(&mut self, translations: &Translations) {
+        if self.built_for_version != translations.version() {
+            self.menu.entries = Self::entries(translations);
+            self.built_for_version = translations.version();
+        }
     }
 }
 
@@ -29,16 +56,19 @@ pub fn draw_main_menu_scene(
     canvas: &mut Canvas<Window>,
     font_manager: &mut FontManager,
     state: &MainMenuState,
+    translations: &Translations,
+    theme: &Theme,
 ) -> Result<(), String> {
-    let options = ["Study", "Profile", "Quit"];
-    font_manager.draw_single_line(canvas, "CardBrick", 20, 20)?;
+    font_manager.set_text_color(theme.text);
+    font_manager.draw_single_line(canvas, translations.tr(StringId::MenuTitle), 20, 20)?;
 
     let mut y_pos = 150;
-    for (i, option) in options.iter().enumerate() {
-        if i == state.selected_index {
+    for (i, entry) in state.menu.entries.iter().enumerate() {
+        let option = entry.label();
+        if i == state.menu.selected {
             let (text_w, text_h) = font_manager.size_of_text(option)?;
             let highlight_rect = Rect::new(18, y_pos, text_w + 4, text_h);
-            canvas.set_draw_color(Color::RGB(80, 80, 80));
+            canvas.set_draw_color(theme.menu_highlight);
             canvas.fill_rect(highlight_rect)?;
         }
         font_manager.draw_single_line(canvas, option, 20, y_pos)?;
@@ -0,0 +1,111 @@
+// src/scenes/options/input.rs
+
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+
+use crate::settings::{RatingButtonLayout, Settings};
+use crate::sound::Sfx;
+use crate::state::{map_to_brick_input, BrickInput, BrickButton};
+use crate::{AppState, GameState};
+
+use super::{OptionRow, OPTION_ROWS, NEW_CARDS_STEP, VOLUME_STEP};
+
+pub fn handle_options_input(state: &mut AppState, event: Event) -> Result<(), String> {
+    if let Some(input) = map_to_brick_input(&event, &mut state.config.key_bindings) {
+        match input {
+            BrickInput::ButtonDown(BrickButton::DPadDown) => {
+                if let GameState::Options(options_state) = &mut state.game_state {
+                    options_state.selected_index = (options_state.selected_index + 1).min(OPTION_ROWS.len() - 1);
+                }
+                state.sound.play_sfx(Sfx::UpDown);
+            }
+            BrickInput::ButtonDown(BrickButton::DPadUp) => {
+                if let GameState::Options(options_state) = &mut state.game_state {
+                    options_state.selected_index = options_state.selected_index.saturating_sub(1);
+                }
+                state.sound.play_sfx(Sfx::UpDown);
+            }
+            BrickInput::ButtonDown(BrickButton::DPadLeft) => adjust_selected(state, -1),
+            BrickInput::ButtonDown(BrickButton::DPadRight) => adjust_selected(state, 1),
+            BrickInput::ButtonDown(BrickButton::B) | BrickInput::ButtonDown(BrickButton::Start) => {
+                close_options(state);
+            }
+            _ => {}
+        }
+    } else if let Event::KeyDown { keycode: Some(keycode), repeat: false, .. } = event {
+        match keycode {
+            Keycode::Up => {
+                if let GameState::Options(options_state) = &mut state.game_state {
+                    options_state.selected_index = options_state.selected_index.saturating_sub(1);
+                }
+                state.sound.play_sfx(Sfx::UpDown);
+            }
+            Keycode::Down => {
+                if let GameState::Options(options_state) = &mut state.game_state {
+                    options_state.selected_index = (options_state.selected_index + 1).min(OPTION_ROWS.len() - 1);
+                }
+                state.sound.play_sfx(Sfx::UpDown);
+            }
+            Keycode::Left => adjust_selected(state, -1),
+            Keycode::Right => adjust_selected(state, 1),
+            Keycode::Backspace | Keycode::Return => close_options(state),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Adjusts the currently-selected row by `direction` (-1 or 1), applies any
+/// effect that takes hold immediately (volume, no restart needed), and
+/// persists the change so a crash doesn't lose it.
+fn adjust_selected(state: &mut AppState, direction: i32) {
+    let selected = if let GameState::Options(options_state) = &state.game_state {
+        OPTION_ROWS[options_state.selected_index]
+    } else {
+        return;
+    };
+
+    adjust_row(&mut state.settings, selected, direction);
+
+    match selected {
+        OptionRow::SfxVolume => state.sound.set_sfx_volume(state.settings.sfx_volume),
+        OptionRow::BgmVolume => state.sound.set_music_volume(state.settings.bgm_volume),
+        _ => {}
+    }
+
+    state.settings.save(&state.config.settings_path).ok();
+}
+
+fn adjust_row(settings: &mut Settings, row: OptionRow, direction: i32) {
+    match row {
+        OptionRow::FontScale => {
+            settings.font_scale = (settings.font_scale + direction as f32 * VOLUME_STEP).clamp(0.5, 2.0);
+        }
+        OptionRow::SfxVolume => {
+            settings.sfx_volume = (settings.sfx_volume + direction as f32 * VOLUME_STEP).clamp(0.0, 1.0);
+        }
+        OptionRow::BgmVolume => {
+            settings.bgm_volume = (settings.bgm_volume + direction as f32 * VOLUME_STEP).clamp(0.0, 1.0);
+        }
+        OptionRow::ShowRubyByDefault => {
+            settings.show_ruby_by_default = !settings.show_ruby_by_default;
+        }
+        OptionRow::NewCardsPerDay => {
+            settings.new_cards_per_day = (settings.new_cards_per_day as i32 + direction * NEW_CARDS_STEP as i32).max(0) as u32;
+        }
+        OptionRow::RatingButtonLayout => {
+            settings.rating_button_layout = match settings.rating_button_layout {
+                RatingButtonLayout::Standard => RatingButtonLayout::Swapped,
+                RatingButtonLayout::Swapped => RatingButtonLayout::Standard,
+            };
+        }
+    }
+}
+
+/// Pops back to whatever scene Start was pressed from.
+fn close_options(state: &mut AppState) {
+    let placeholder = GameState::Error("transitioning".to_string());
+    if let GameState::Options(options_state) = std::mem::replace(&mut state.game_state, placeholder) {
+        state.game_state = *options_state.return_to;
+    }
+}
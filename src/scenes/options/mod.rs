@@ -0,0 +1,119 @@
+// src/scenes/options/mod.rs
+
+use sdl2::rect::Rect;
+use sdl2::render::Canvas;
+use sdl2::video::Window;
+
+use crate::i18n::{StringId, Translations};
+use crate::settings::{RatingButtonLayout, Settings};
+use crate::state::GameState;
+use crate::ui::{FontManager, Theme};
+
+pub mod input;
+
+/// How far one D-pad press adjusts a percentage-like setting (volume).
+pub const VOLUME_STEP: f32 = 0.1;
+/// How far one D-pad press adjusts the cards-per-day cap.
+pub const NEW_CARDS_STEP: u32 = 5;
+
+/// One adjustable row in the options list, in the order they're drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OptionRow {
+    FontScale,
+    SfxVolume,
+    BgmVolume,
+    ShowRubyByDefault,
+    NewCardsPerDay,
+    RatingButtonLayout,
+}
+
+pub const OPTION_ROWS: [OptionRow; 6] = [
+    OptionRow::FontScale,
+    OptionRow::SfxVolume,
+    OptionRow::BgmVolume,
+    OptionRow::ShowRubyByDefault,
+    OptionRow::NewCardsPerDay,
+    OptionRow::RatingButtonLayout,
+];
+
+/// Contains the state specific to the options screen.
+pub struct OptionsState<'a> {
+    pub selected_index: usize,
+    /// The scene Start was pressed from, restored when the options scene closes.
+    pub return_to: Box<GameState<'a>>,
+}
+
+impl<'a> OptionsState<'a> {
+    pub fn new(return_to: GameState<'a>) -> Self {
+        Self {
+            selected_index: 0,
+            return_to: Box::new(return_to),
+        }
+    }
+}
+
+/// Formats one row's current value for display.
+fn row_label(row: OptionRow, settings: &Settings, translations: &Translations) -> String {
+    match row {
+        OptionRow::FontScale => translations.tr_with(
+            StringId::OptionFontScale,
+            &[("scale", &format!("{:.1}", settings.font_scale))],
+        ),
+        OptionRow::SfxVolume => translations.tr_with(
+            StringId::OptionSfxVolume,
+            &[("percent", &(settings.sfx_volume * 100.0).round().to_string())],
+        ),
+        OptionRow::BgmVolume => translations.tr_with(
+            StringId::OptionBgmVolume,
+            &[("percent", &(settings.bgm_volume * 100.0).round().to_string())],
+        ),
+        OptionRow::ShowRubyByDefault => {
+            let state = if settings.show_ruby_by_default {
+                translations.tr(StringId::ToggleOn)
+            } else {
+                translations.tr(StringId::ToggleOff)
+            };
+            translations.tr_with(StringId::OptionShowRuby, &[("state", state)])
+        }
+        OptionRow::NewCardsPerDay => translations.tr_with(
+            StringId::OptionNewCardsPerDay,
+            &[("count", &settings.new_cards_per_day.to_string())],
+        ),
+        OptionRow::RatingButtonLayout => {
+            let layout = match settings.rating_button_layout {
+                RatingButtonLayout::Standard => translations.tr(StringId::RatingButtonsStandard),
+                RatingButtonLayout::Swapped => translations.tr(StringId::RatingButtonsSwapped),
+            };
+            translations.tr_with(StringId::OptionRatingButtons, &[("layout", layout)])
+        }
+    }
+}
+
+/// Draws the options scene: a simple D-pad-navigable list, the selected row
+/// highlighted the same way `main_menu` highlights its selection.
+pub fn draw_options_scene(
+    canvas: &mut Canvas<Window>,
+    font_manager: &mut FontManager,
+    options_state: &OptionsState,
+    settings: &Settings,
+    translations: &Translations,
+    theme: &Theme,
+) -> Result<(), String> {
+    font_manager.set_text_color(theme.text);
+    font_manager.draw_single_line(canvas, translations.tr(StringId::OptionsTitle), 20, 20)?;
+
+    let mut y_pos = 80;
+    for (i, row) in OPTION_ROWS.iter().enumerate() {
+        let label = row_label(*row, settings, translations);
+        if i == options_state.selected_index {
+            let (text_w, text_h) = font_manager.size_of_text(&label)?;
+            let highlight_rect = Rect::new(18, y_pos - 2, text_w + 8, text_h + 4);
+            canvas.set_draw_color(theme.menu_highlight);
+            canvas.fill_rect(highlight_rect)?;
+            font_manager.set_text_color(theme.text);
+        }
+        font_manager.draw_single_line(canvas, &label, 20, y_pos)?;
+        y_pos += 40;
+    }
+    Ok(())
+}
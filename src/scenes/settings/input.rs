@@ -0,0 +1,77 @@
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+
+use crate::menu::MenuSelectionResult;
+use crate::scenes::main_menu::MainMenuState;
+use crate::scenes::settings::apply_to_settings;
+use crate::sound::Sfx;
+use crate::state::{map_to_brick_input, BrickInput, BrickButton};
+use crate::{AppState, GameState};
+
+pub fn handle_settings_input(state: &mut AppState, event: Event) -> Result<(), String> {
+    let mut moved = false;
+    let mut toggled = false;
+    let mut canceled = false;
+
+    if let GameState::Settings(settings_menu) = &mut state.game_state {
+        if let Some(input) = map_to_brick_input(&event, &mut state.config.key_bindings) {
+            moved = matches!(
+                input,
+                BrickInput::ButtonDown(BrickButton::DPadDown) | BrickInput::ButtonDown(BrickButton::DPadUp)
+            );
+            match settings_menu.menu.process_input(input) {
+                MenuSelectionResult::Selected(_, _) => toggled = true,
+                MenuSelectionResult::Canceled => canceled = true,
+                MenuSelectionResult::None => {}
+            }
+        } else if let Event::KeyDown { keycode: Some(key), repeat: false, .. } = event {
+            match key {
+                Keycode::Up => {
+                    settings_menu.menu.process_input(BrickInput::ButtonDown(BrickButton::DPadUp));
+                    moved = true;
+                }
+                Keycode::Down => {
+                    settings_menu.menu.process_input(BrickInput::ButtonDown(BrickButton::DPadDown));
+                    moved = true;
+                }
+                Keycode::Return => {
+                    if let MenuSelectionResult::Selected(_, _) =
+                        settings_menu.menu.process_input(BrickInput::ButtonDown(BrickButton::A))
+                    {
+                        toggled = true;
+                    }
+                }
+                Keycode::Escape | Keycode::Backspace => canceled = true,
+                _ => {}
+            }
+        } else if let Event::KeyUp { keycode: Some(key), .. } = event {
+            // Stops `Menu::tick`'s auto-repeat once an arrow key typed
+            // outside the binding table (see the `KeyDown` arm above) is
+            // released.
+            match key {
+                Keycode::Up => { settings_menu.menu.process_input(BrickInput::ButtonUp(BrickButton::DPadUp)); }
+                Keycode::Down => { settings_menu.menu.process_input(BrickInput::ButtonUp(BrickButton::DPadDown)); }
+                _ => {}
+            }
+        }
+    }
+
+    if moved {
+        state.sound.play_sfx(Sfx::UpDown);
+    }
+
+    if toggled {
+        if let GameState::Settings(settings_menu) = &state.game_state {
+            apply_to_settings(settings_menu, &mut state.settings);
+        }
+        state.sound.set_sfx_enabled(state.settings.sfx_enabled);
+        state.translations.set_language(&state.settings.language);
+        state.settings.save(&state.config.settings_path).ok();
+    }
+
+    if canceled {
+        state.game_state = GameState::MainMenu(MainMenuState::new(&state.translations));
+    }
+
+    Ok(())
+}
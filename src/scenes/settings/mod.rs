@@ -0,0 +1,118 @@
+// src/scenes/settings/mod.rs
+
+use sdl2::rect::Rect;
+use sdl2::render::Canvas;
+use sdl2::video::Window;
+
+use crate::i18n::{StringId, Translations};
+use crate::menu::{Menu, MenuEntry};
+use crate::settings::Settings;
+use crate::ui::{FontManager, Theme};
+
+pub mod input;
+
+/// One settings toggle the screen shows, in display order. Matches
+/// `SettingsMenuState::menu`'s entries 1:1 by index, the same way
+/// `controls_menu::REBINDABLE_BUTTONS` lines up with its own menu — keyed
+/// off this enum rather than the row's display label, so relabeling or
+/// localizing a row can't silently stop it from being persisted.
+const SETTINGS_ROWS: [SettingsRow; 4] =
+    [SettingsRow::Sfx, SettingsRow::MenuSound, SettingsRow::PauseOnFocusLoss, SettingsRow::Japanese];
+
+#[derive(Debug, Clone, Copy)]
+enum SettingsRow {
+    Sfx,
+    MenuSound,
+    PauseOnFocusLoss,
+    /// On picks the "ja" language table, off picks "en" — the only two
+    /// `Translations` ships, so a plain toggle covers it without needing a
+    /// cycle-through-values row type.
+    Japanese,
+}
+
+/// Contains the state specific to the settings (persistent toggles) screen.
+pub struct SettingsMenuState {
+    pub menu: Menu,
+}
+
+impl SettingsMenuState {
+    pub fn new(settings: &Settings, translations: &Translations) -> Self {
+        let entries = vec![
+            MenuEntry::Toggle(translations.tr(StringId::SettingsSfx).to_string(), settings.sfx_enabled),
+            MenuEntry::Toggle(
+                translations.tr(StringId::SettingsMenuSound).to_string(),
+                settings.menu_sound_enabled,
+            ),
+            MenuEntry::Toggle(
+                translations.tr(StringId::SettingsPauseOnFocusLoss).to_string(),
+                settings.pause_on_focus_loss,
+            ),
+            MenuEntry::Toggle(
+                translations.tr(StringId::SettingsJapanese).to_string(),
+                settings.language == "ja",
+            ),
+        ];
+        Self { menu: Menu::new(entries) }
+    }
+}
+
+/// Applies whatever `state.menu`'s `Toggle` rows currently hold back onto
+/// `settings`, matched up with `SETTINGS_ROWS` by index rather than by the
+/// row's display label.
+pub fn apply_to_settings(state: &SettingsMenuState, settings: &mut Settings) {
+    for (entry, row) in state.menu.entries.iter().zip(SETTINGS_ROWS.iter()) {
+        if let MenuEntry::Toggle(_, value) = entry {
+            match row {
+                SettingsRow::Sfx => settings.sfx_enabled = *value,
+                SettingsRow::MenuSound => settings.menu_sound_enabled = *value,
+                SettingsRow::PauseOnFocusLoss => settings.pause_on_focus_loss = *value,
+                SettingsRow::Japanese => settings.language = if *value { "ja" } else { "en" }.to_string(),
+            }
+        }
+    }
+}
+
+/// Formats a row as `"<label>: On"`/`"<label>: Off"` for `Toggle` entries,
+/// or its plain label otherwise, matching `controls_menu`'s row style.
+fn row_label(entry: &MenuEntry, translations: &Translations) -> String {
+    match entry {
+        MenuEntry::Toggle(label, value) => {
+            let state = if *value {
+                translations.tr(StringId::ToggleOn)
+            } else {
+                translations.tr(StringId::ToggleOff)
+            };
+            format!("{}: {}", label, state)
+        }
+        _ => entry.label().to_string(),
+    }
+}
+
+/// Draws the settings scene: a D-pad-navigable list of on/off toggles,
+/// styled the same way `controls_menu` draws its rows.
+pub fn draw_settings_scene(
+    canvas: &mut Canvas<Window>,
+    font_manager: &mut FontManager,
+    state: &SettingsMenuState,
+    translations: &Translations,
+    theme: &Theme,
+) -> Result<(), String> {
+    font_manager.set_text_color(theme.text);
+    font_manager.draw_single_line(canvas, translations.tr(StringId::SettingsTitle), 20, 20)?;
+
+    let mut y_pos = 80;
+    for (i, entry) in state.menu.entries.iter().enumerate() {
+        let label = row_label(entry, translations);
+        if i == state.menu.selected {
+            let (text_w, text_h) = font_manager.size_of_text(&label)?;
+            let highlight_rect = Rect::new(18, y_pos - 2, text_w + 8, text_h + 4);
+            canvas.set_draw_color(theme.menu_highlight);
+            canvas.fill_rect(highlight_rect)?;
+            font_manager.set_text_color(theme.text);
+        }
+        font_manager.draw_single_line(canvas, &label, 20, y_pos)?;
+        y_pos += 40;
+    }
+
+    Ok(())
+}
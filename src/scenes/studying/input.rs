@@ -2,19 +2,85 @@
 
 use crate::BrickInput;
 use crate::BrickButton;
+use crate::state::BrickAxis;
 use crate::map_to_brick_input;
 use sdl2::event::Event;
 use sdl2::keyboard::Keycode;
 
 use crate::{AppState, GameState};
 use crate::deck::html_parser;
+use crate::i18n::{StringId, Translations};
+use crate::scenes::options::OptionsState;
 use crate::scheduler::Rating;
+use crate::settings::RatingButtonLayout;
 use super::logic::{load_card_layouts, load_next_card};
 
+/// The button-to-rating legend shown once the answer is revealed, matching
+/// whichever `RatingButtonLayout` is active so the on-screen labels never lie
+/// about what `keycode_to_rating` actually does. The rating words themselves
+/// come from `translations` rather than being hardcoded, so a non-English
+/// locale doesn't get an English fragment stitched into an otherwise
+/// translated hint string.
+fn rating_hint_prefix(layout: RatingButtonLayout, translations: &Translations) -> String {
+    let (again, hard, good, easy) = (
+        translations.tr(StringId::RatingAgain),
+        translations.tr(StringId::RatingHard),
+        translations.tr(StringId::RatingGood),
+        translations.tr(StringId::RatingEasy),
+    );
+    match layout {
+        RatingButtonLayout::Standard => format!("A:{good} B:{again} X:{easy} Y:{hard}"),
+        RatingButtonLayout::Swapped => format!("A:{again} B:{good} X:{hard} Y:{easy}"),
+    }
+}
+
+/// Maps a rating keycode to a `Rating` according to the active
+/// `RatingButtonLayout` (see `rating_hint_prefix` for the matching on-screen labels).
+fn keycode_to_rating(keycode: Keycode, layout: RatingButtonLayout) -> Option<Rating> {
+    match layout {
+        RatingButtonLayout::Standard => match keycode {
+            Keycode::B => Some(Rating::Again),
+            Keycode::Y => Some(Rating::Hard),
+            Keycode::A => Some(Rating::Good),
+            Keycode::X => Some(Rating::Easy),
+            _ => None,
+        },
+        RatingButtonLayout::Swapped => match keycode {
+            Keycode::A => Some(Rating::Again),
+            Keycode::X => Some(Rating::Hard),
+            Keycode::B => Some(Rating::Good),
+            Keycode::Y => Some(Rating::Easy),
+            _ => None,
+        },
+    }
+}
+
+/// Maps a gamepad rating button to a `Rating` according to the active
+/// `RatingButtonLayout`, mirroring `keycode_to_rating` so the gamepad and
+/// keyboard paths agree on which face button means what.
+fn brick_button_to_rating(button: BrickButton, layout: RatingButtonLayout) -> Option<Rating> {
+    match layout {
+        RatingButtonLayout::Standard => match button {
+            BrickButton::B => Some(Rating::Again),
+            BrickButton::Y => Some(Rating::Hard),
+            BrickButton::A => Some(Rating::Good),
+            BrickButton::X => Some(Rating::Easy),
+            _ => None,
+        },
+        RatingButtonLayout::Swapped => match button {
+            BrickButton::A => Some(Rating::Again),
+            BrickButton::X => Some(Rating::Hard),
+            BrickButton::B => Some(Rating::Good),
+            BrickButton::Y => Some(Rating::Easy),
+            _ => None,
+        },
+    }
+}
+
 /// Handles input events for the studying scene.
 pub fn handle_studying_input(state: &mut AppState, event: Event) -> Result<(), String> {
     if let GameState::Studying(studying_state) = &mut state.game_state {
-        if let Some(input) = map_to_brick_input(&event) {
+        if let Some(input) = map_to_brick_input(&event, &mut state.config.key_bindings) {
             match input {
                 BrickInput::ButtonDown(BrickButton::DPadDown) => {
                     match studying_state.is_answer_revealed {
@@ -24,7 +90,9 @@ pub fn handle_studying_input(state: &mut AppState, event: Event) -> Result<(), S
                         _ => {
                             studying_state.is_answer_revealed = true;
                             let margin: u32 = 30;
-                            let hint_spans = html_parser::parse_html_to_spans("A:Good B:Again X:Easy Y:Hard [LB:Rewind] [RB:Ruby]");
+                            let legend = rating_hint_prefix(state.settings.rating_button_layout, &state.translations);
+                            let hint_text = state.translations.tr_with(StringId::StudyHintGamepad, &[("legend", &legend)]);
+                            let hint_spans = html_parser::parse_html_to_spans(&hint_text);
                             studying_state.hint_layout = Some(state.hint_font_manager.layout_text_binary(&hint_spans, state.config.window_width / 2 - margin * 2, studying_state.show_ruby_text)?);
                         }
                     }
@@ -33,15 +101,20 @@ pub fn handle_studying_input(state: &mut AppState, event: Event) -> Result<(), S
                 },
                 BrickInput::ButtonDown(BrickButton::A) => {
                     if studying_state.is_answer_revealed {
-                        let rating = Some(Rating::Good);
+                        let rating = brick_button_to_rating(BrickButton::A, state.settings.rating_button_layout);
                         if let Some(r) = rating {
                             if let Some(card) = &studying_state.current_card {
-                                if let Some(updated_card) = studying_state.scheduler.answer_card(card.id, r) {
+                                if let Some(updated_card) = studying_state.scheduler.answer_card(card.id, r, crate::scheduler::current_day_number()) {
                                     studying_state.replay_logger.log_action(&updated_card, r).map_err(|e| e.to_string())?;
                                     studying_state.db_manager.update_card_state(&updated_card).map_err(|e| e.to_string())?;
+                                    studying_state.db_manager.log_review(card.id, r, card.interval, updated_card.interval).map_err(|e| e.to_string())?;
+                                    state.sprite.react(r);
                                 }
                             }
-                            load_next_card(studying_state, &mut state.font_manager, &mut state.small_font_manager);
+                            load_next_card(studying_state, &mut state.font_manager, &mut state.small_font_manager, &state.translations, &state.config);
+                            if studying_state.is_done {
+                                state.sprite.session_complete();
+                            }
                         }
 
                     }
@@ -49,49 +122,66 @@ pub fn handle_studying_input(state: &mut AppState, event: Event) -> Result<(), S
                 },
                 BrickInput::ButtonDown(BrickButton::B) => {
                     if studying_state.is_answer_revealed {
-                        let rating = Some(Rating::Good);
+                        let rating = brick_button_to_rating(BrickButton::B, state.settings.rating_button_layout);
                         if let Some(r) = rating {
                             if let Some(card) = &studying_state.current_card {
-                                if let Some(updated_card) = studying_state.scheduler.answer_card(card.id, r) {
+                                if let Some(updated_card) = studying_state.scheduler.answer_card(card.id, r, crate::scheduler::current_day_number()) {
                                     studying_state.replay_logger.log_action(&updated_card, r).map_err(|e| e.to_string())?;
                                     studying_state.db_manager.update_card_state(&updated_card).map_err(|e| e.to_string())?;
+                                    studying_state.db_manager.log_review(card.id, r, card.interval, updated_card.interval).map_err(|e| e.to_string())?;
+                                    state.sprite.react(r);
                                 }
                             }
-                            load_next_card(studying_state, &mut state.font_manager, &mut state.small_font_manager);
+                            load_next_card(studying_state, &mut state.font_manager, &mut state.small_font_manager, &state.translations, &state.config);
+                            if studying_state.is_done {
+                                state.sprite.session_complete();
+                            }
                         }
                     }
                 },
                 BrickInput::ButtonDown(BrickButton::X) => {
                     if studying_state.is_answer_revealed {
-                        let rating = Some(Rating::Good);
+                        let rating = brick_button_to_rating(BrickButton::X, state.settings.rating_button_layout);
                         if let Some(r) = rating {
                             if let Some(card) = &studying_state.current_card {
-                                if let Some(updated_card) = studying_state.scheduler.answer_card(card.id, r) {
+                                if let Some(updated_card) = studying_state.scheduler.answer_card(card.id, r, crate::scheduler::current_day_number()) {
                                     studying_state.replay_logger.log_action(&updated_card, r).map_err(|e| e.to_string())?;
                                     studying_state.db_manager.update_card_state(&updated_card).map_err(|e| e.to_string())?;
+                                    studying_state.db_manager.log_review(card.id, r, card.interval, updated_card.interval).map_err(|e| e.to_string())?;
+                                    state.sprite.react(r);
                                 }
                             }
-                            load_next_card(studying_state, &mut state.font_manager, &mut state.small_font_manager);
+                            load_next_card(studying_state, &mut state.font_manager, &mut state.small_font_manager, &state.translations, &state.config);
+                            if studying_state.is_done {
+                                state.sprite.session_complete();
+                            }
                         }
                     }
                 },
                 BrickInput::ButtonDown(BrickButton::Y) => {
                     if studying_state.is_answer_revealed {
-                        let rating = Some(Rating::Good);
+                        let rating = brick_button_to_rating(BrickButton::Y, state.settings.rating_button_layout);
                         if let Some(r) = rating {
                             if let Some(card) = &studying_state.current_card {
-                                if let Some(updated_card) = studying_state.scheduler.answer_card(card.id, r) {
+                                if let Some(updated_card) = studying_state.scheduler.answer_card(card.id, r, crate::scheduler::current_day_number()) {
                                     studying_state.replay_logger.log_action(&updated_card, r).map_err(|e| e.to_string())?;
                                     studying_state.db_manager.update_card_state(&updated_card).map_err(|e| e.to_string())?;
+                                    studying_state.db_manager.log_review(card.id, r, card.interval, updated_card.interval).map_err(|e| e.to_string())?;
+                                    state.sprite.react(r);
                                 }
                             }
-                            load_next_card(studying_state, &mut state.font_manager, &mut state.small_font_manager);
+                            load_next_card(studying_state, &mut state.font_manager, &mut state.small_font_manager, &state.translations, &state.config);
+                            if studying_state.is_done {
+                                state.sprite.session_complete();
+                            }
                         }
                     }
                 },
 
                 BrickInput::ButtonDown(BrickButton::Start) => {
-                    // We probably want to show an options screen
+                    let previous = std::mem::replace(&mut state.game_state, GameState::Error("transitioning".to_string()));
+                    state.game_state = GameState::Options(OptionsState::new(previous));
+                    return Ok(());
                 },
                 BrickInput::ButtonDown(BrickButton::LeftShoulder) => {
                     if let Some(card) = &studying_state.current_card {
@@ -99,9 +189,9 @@ pub fn handle_studying_input(state: &mut AppState, event: Event) -> Result<(), S
                     }
                     if let Some(rewound_card) = studying_state.scheduler.rewind_last_answer() {
                         studying_state.current_card = Some(rewound_card.clone());
-                        load_card_layouts(studying_state, &rewound_card, &mut state.font_manager, &mut state.small_font_manager);
+                        load_card_layouts(studying_state, &rewound_card, &mut state.font_manager, &mut state.small_font_manager, &state.config);
                     } else {
-                        load_next_card(studying_state, &mut state.font_manager, &mut state.small_font_manager);
+                        load_next_card(studying_state, &mut state.font_manager, &mut state.small_font_manager, &state.translations, &state.config);
                     }
                     return Ok(());
                 },
@@ -114,6 +204,9 @@ pub fn handle_studying_input(state: &mut AppState, event: Event) -> Result<(), S
                     return Ok(());
                 },
 
+                BrickInput::AxisMotion { axis: BrickAxis::TriggerLeft, value } => studying_state.trigger_left = value,
+                BrickInput::AxisMotion { axis: BrickAxis::TriggerRight, value } => studying_state.trigger_right = value,
+
                 _ => {}
             }
         } 
@@ -132,21 +225,20 @@ pub fn handle_studying_input(state: &mut AppState, event: Event) -> Result<(), S
             }
 
             if studying_state.is_answer_revealed {
-                let rating = match keycode {
-                    Keycode::B => Some(Rating::Again),
-                    Keycode::Y => Some(Rating::Hard),
-                    Keycode::A => Some(Rating::Good),
-                    Keycode::X => Some(Rating::Easy),
-                    _ => None,
-                };
+                let rating = keycode_to_rating(keycode, state.settings.rating_button_layout);
                 if let Some(r) = rating {
                     if let Some(card) = &studying_state.current_card {
-                        if let Some(updated_card) = studying_state.scheduler.answer_card(card.id, r) {
+                        if let Some(updated_card) = studying_state.scheduler.answer_card(card.id, r, crate::scheduler::current_day_number()) {
                             studying_state.replay_logger.log_action(&updated_card, r).map_err(|e| e.to_string())?;
                             studying_state.db_manager.update_card_state(&updated_card).map_err(|e| e.to_string())?;
+                            studying_state.db_manager.log_review(card.id, r, card.interval, updated_card.interval).map_err(|e| e.to_string())?;
+                            state.sprite.react(r);
                         }
                     }
-                    load_next_card(studying_state, &mut state.font_manager, &mut state.small_font_manager);
+                    load_next_card(studying_state, &mut state.font_manager, &mut state.small_font_manager, &state.translations, &state.config);
+                    if studying_state.is_done {
+                        state.sprite.session_complete();
+                    }
                 } else {
                     // Handle scrolling
                     let scroll_speed = 30;
@@ -167,7 +259,9 @@ pub fn handle_studying_input(state: &mut AppState, event: Event) -> Result<(), S
                 // Reveal answer
                 studying_state.is_answer_revealed = true;
                 let margin: u32 = 30;
-                let hint_spans = html_parser::parse_html_to_spans("A:Good B:Again X:Easy Y:Hard [Enter:Rewind]");
+                let legend = rating_hint_prefix(state.settings.rating_button_layout, &state.translations);
+                let hint_text = state.translations.tr_with(StringId::StudyHintKeyboard, &[("legend", &legend)]);
+                let hint_spans = html_parser::parse_html_to_spans(&hint_text);
                 studying_state.hint_layout = Some(state.hint_font_manager.layout_text_binary(&hint_spans, state.config.window_width / 2 - margin * 2, studying_state.show_ruby_text)?);
             }
         }
@@ -1,40 +1,90 @@
 // src/scenes/studying/logic.rs
 
-use crate::deck::{html_parser, Card};
+use sdl2::mixer::{Channel, Chunk};
+
+use crate::config::Config;
+use crate::deck::{html_parser, Card, MediaRef};
 use crate::debug::Tracer;
+use crate::i18n::{StringId, Translations};
 use crate::ui::FontManager;
 use super::StudyingState;
 
 /// Loads the next card from the scheduler into the state.
-pub fn load_next_card(state: &mut StudyingState, font: &mut FontManager, small_font: &mut FontManager) {
+pub fn load_next_card(state: &mut StudyingState, font: &mut FontManager, small_font: &mut FontManager, translations: &Translations, config: &Config) {
     state.current_card = state.scheduler.next_card();
     if let Some(card) = state.current_card.clone() {
-        load_card_layouts(state, &card, font, small_font);
+        load_card_layouts(state, &card, font, small_font, config);
     } else {
         state.is_done = true;
-        let done_spans = html_parser::parse_html_to_spans("Deck Complete!");
+        let summary_html = session_summary_html(state, translations);
+        let done_spans = html_parser::parse_html_to_spans(&summary_html);
         state.done_layout = font.layout_text_binary(&done_spans, 400_u32, false).ok();
     }
 }
 
+/// Builds the end-of-session summary shown in place of the plain "Deck
+/// Complete!" message: how much was reviewed, the again-rate, and when the
+/// deck will next have cards due, so the user knows whether to come back
+/// later today or another day.
+fn session_summary_html(state: &StudyingState, translations: &Translations) -> String {
+    let reviewed = state.scheduler.reviews_complete();
+    let again = state.scheduler.again_count();
+    let graded = reviewed + again;
+    let again_rate = if graded > 0 { (again * 100 + graded / 2) / graded } else { 0 };
+
+    let next_due = match state.scheduler.next_due_day(crate::scheduler::current_day_number()) {
+        Some(0) => translations.tr(StringId::SessionMoreDueToday).to_string(),
+        Some(1) => translations.tr(StringId::SessionDueTomorrow).to_string(),
+        Some(days) => translations.tr_with(StringId::SessionDueInDays, &[("days", &days.to_string())]),
+        None => translations.tr(StringId::SessionNoMoreScheduled).to_string(),
+    };
+
+    format!(
+        "{}<br>{} reviewed \u{b7} {}% again<br>{}",
+        translations.tr(StringId::DeckComplete),
+        reviewed,
+        again_rate,
+        next_due,
+    )
+}
+
 /// Generates and caches all text layouts for the current card.
-pub fn load_card_layouts(state: &mut StudyingState, card: &Card, font: &mut FontManager, small_font: &mut FontManager) {
+pub fn load_card_layouts(state: &mut StudyingState, card: &Card, font: &mut FontManager, small_font: &mut FontManager, config: &Config) {
     #[cfg(debug_assertions)]
     let _layout_tracer = Tracer::new("Load Card Layout");
     state.is_answer_revealed = false;
     state.scroll_offset = 0;
     state.hint_layout = None;
+    state.current_images.clear();
+    state.current_audio.clear();
 
     if let Some(note) = state.scheduler.get_note(card.note_id) {
-        let content_width = 512 - 60;
+        let content_width = config.profile.logical_width - config.profile.content_margin;
         let front_html = note.fields.get(0).map_or("", |s| s.as_str());
         let back_html = note.fields.get(1).map_or("", |s| s.as_str());
-        
+
         state.front_layout_default = font.layout_text_binary(&html_parser::parse_html_to_spans(front_html), content_width, false).ok();
         state.small_front_layout_default = small_font.layout_text_binary(&html_parser::parse_html_to_spans(front_html), content_width, false).ok();
         state.back_layout_default = font.layout_text_binary(&html_parser::parse_html_to_spans(back_html), content_width, false).ok();
         state.front_layout_ruby = font.layout_text_binary(&html_parser::parse_html_to_spans(front_html), content_width, true).ok();
         state.small_front_layout_ruby = small_font.layout_text_binary(&html_parser::parse_html_to_spans(front_html), content_width, true).ok();
         state.back_layout_ruby = font.layout_text_binary(&html_parser::parse_html_to_spans(back_html), content_width, true).ok();
+
+        for media in &note.media {
+            match media {
+                MediaRef::Image(path) => state.current_images.push(path.clone()),
+                MediaRef::Audio(path) => {
+                    if let Ok(chunk) = Chunk::from_file(path) {
+                        state.current_audio.push(chunk);
+                    }
+                }
+            }
+        }
+        // Autoplay the note's audio as soon as the card appears, matching
+        // how desktop Anki plays a card's sound on reveal. Playback errors
+        // (e.g. no free channel) are non-fatal — the card is still usable.
+        for chunk in &state.current_audio {
+            let _ = Channel::all().play(chunk, 0);
+        }
     }
 }
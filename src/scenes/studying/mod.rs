@@ -1,14 +1,18 @@
 // src/scenes/studying/mod.rs
 
-use sdl2::pixels::Color;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use sdl2::mixer::Chunk;
 use sdl2::rect::Rect;
 use sdl2::render::{Canvas};
 use sdl2::video::Window;
 
 use crate::deck::Card;
+use crate::i18n::{StringId, Translations};
 use crate::scheduler::{Scheduler, Rating};
 use crate::storage::{DatabaseManager, ReplayLogger};
-use crate::ui::{FontManager, font::TextLayout, sprite::Sprite};
+use crate::ui::{FontManager, MediaCache, Theme, font::TextLayout, sprite::Sprite};
 
 pub mod input;
 pub mod logic;
@@ -31,8 +35,31 @@ pub struct StudyingState<'a> {
     pub small_front_layout_ruby: Option<TextLayout>,
     pub hint_layout: Option<TextLayout>,
     pub done_layout: Option<TextLayout>,
+    /// Latest `BrickAxis::TriggerLeft`/`TriggerRight` values (0.0 when idle
+    /// or released), updated on `AxisMotion` events and consumed once per
+    /// frame by `apply_axis_scroll`.
+    pub trigger_left: f32,
+    pub trigger_right: f32,
+    /// Images the current card's note references, in field order. Drawn
+    /// below the text layout inside `content_viewport`.
+    pub current_images: Vec<PathBuf>,
+    /// Audio chunks for the current card's note, decoded up front and kept
+    /// alive here so playback (started in `logic::load_card_layouts`) isn't
+    /// cut short when the local that triggered it goes out of scope.
+    pub current_audio: Vec<Chunk>,
 }
 
+/// Below this magnitude a trigger is treated as released.
+const AXIS_DEADZONE: f32 = 0.12;
+/// Pixels per frame scrolled at full trigger deflection, before the
+/// quadratic acceleration curve is applied.
+const AXIS_SCROLL_PX_PER_FRAME: f32 = 18.0;
+/// Card images are drawn as squares of this side length, keeping the layout
+/// simple regardless of the source picture's aspect ratio.
+const IMAGE_DRAW_SIZE: i32 = 120;
+/// Vertical gap reserved below each drawn image.
+const IMAGE_SPACING: i32 = 16;
+
 impl<'a> StudyingState<'a> {
     /// Creates a new StudyingState.
     pub fn new(scheduler: Box<dyn Scheduler + 'a>, db_manager: DatabaseManager, replay_logger: ReplayLogger) -> Self {
@@ -53,8 +80,74 @@ impl<'a> StudyingState<'a> {
             small_front_layout_ruby: None,
             hint_layout: None,
             done_layout: None,
+            trigger_left: 0.0,
+            trigger_right: 0.0,
+            current_images: Vec::new(),
+            current_audio: Vec::new(),
         }
     }
+
+    /// Consumes the latest `trigger_left`/`trigger_right` values to scroll
+    /// the revealed card body at a rate proportional to (and accelerating
+    /// with) how far a trigger is held. Called once per frame rather than on
+    /// event edges, so holding a trigger keeps scrolling without repeated
+    /// D-pad taps. Only scrolls once the answer is revealed, matching the
+    /// D-pad scroll behavior in `handle_studying_input`.
+    pub fn apply_axis_scroll(&mut self) {
+        if !self.is_answer_revealed {
+            return;
+        }
+        let net = self.trigger_right - self.trigger_left;
+        if net.abs() <= AXIS_DEADZONE {
+            return;
+        }
+
+        let delta = net.signum() * net.abs().powi(2) * AXIS_SCROLL_PX_PER_FRAME;
+        let viewport_height = 290;
+        let text_height = if let (Some(front), Some(back)) = (&self.small_front_layout_default, &self.back_layout_default) {
+            front.total_height + back.total_height + 20
+        } else { 0 };
+        let images_height = self.current_images.len() as i32 * (IMAGE_DRAW_SIZE + IMAGE_SPACING);
+        let total_height = text_height + images_height;
+        let max_scroll = (total_height - viewport_height).max(0);
+        self.scroll_offset = (self.scroll_offset as f32 + delta).clamp(0.0, max_scroll as f32) as i32;
+    }
+}
+
+/// Builds the "Next in 10m · 4 learning · 12 review left" footer drawn next
+/// to the progress bar, or `None` once there's nothing left to forecast.
+fn forecast_line(scheduler: &dyn Scheduler, translations: &Translations) -> Option<String> {
+    let counts = scheduler.queue_counts();
+    let mut parts = Vec::new();
+    if let Some(wait) = scheduler.next_due_in() {
+        let time = format_duration_human(wait);
+        parts.push(translations.tr_with(StringId::ForecastNextIn, &[("time", &time)]));
+    }
+    if counts.learning > 0 {
+        parts.push(translations.tr_with(StringId::ForecastLearning, &[("count", &counts.learning.to_string())]));
+    }
+    if counts.review > 0 {
+        parts.push(translations.tr_with(StringId::ForecastReviewLeft, &[("count", &counts.review.to_string())]));
+    }
+    if parts.is_empty() {
+        None
+    } else {
+        Some(parts.join(" \u{b7} "))
+    }
+}
+
+/// Formats a duration as its single largest whole unit, from seconds to days.
+fn format_duration_human(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    if secs < 60 {
+        format!("{}s", secs.max(1))
+    } else if secs < 3600 {
+        format!("{}m", secs / 60)
+    } else if secs < 86_400 {
+        format!("{}h", secs / 3600)
+    } else {
+        format!("{}d", secs / 86_400)
+    }
 }
 
 /// Draws the studying scene.
@@ -66,38 +159,52 @@ pub fn draw_studying_scene(
     small_font_manager: &mut FontManager,
     hint_font_manager: &mut FontManager,
     sprite: &mut Sprite,
+    media_cache: &mut MediaCache,
+    theme: &Theme,
+    translations: &Translations,
 ) -> Result<(), String> {
+    font_manager.set_text_color(theme.text);
+    small_font_manager.set_text_color(theme.text);
+    hint_font_manager.set_text_color(theme.hint_text);
+
     let margin: u32 = 30;
     let total = studying_state.scheduler.total_session_cards();
     if total > 0 {
         let completed = studying_state.scheduler.reviews_complete();
         let bar_height = 25_u32;
         let bar_bg_rect = Rect::new(0, 0, 512, bar_height);
-        canvas.set_draw_color(Color::RGB(60, 60, 60));
+        canvas.set_draw_color(theme.progress_empty);
         canvas.fill_rect(bar_bg_rect)?;
         let progress = completed as f32 / total as f32;
         let progress_width = (512.0 * progress) as u32;
         let bar_fg_rect = Rect::new(0, 0, progress_width, bar_height);
-        let r = (255.0 * (1.0 - progress)) as u8;
-        let g = (255.0 * progress) as u8;
-        canvas.set_draw_color(Color::RGB(r, g, 80));
+        canvas.set_draw_color(crate::ui::lerp_color(theme.progress_full_low, theme.progress_full_high, progress));
         canvas.fill_rect(bar_fg_rect)?;
         let progress_text = format!("{}/{}", completed, total);
         let (text_w, text_h) = hint_font_manager.size_of_text(&progress_text)?;
         let text_x = (512 as i32 - text_w as i32 - 10).max(0);
         let text_y = (bar_height as i32 - text_h as i32) / 2;
         hint_font_manager.draw_single_line(canvas, &progress_text, text_x, text_y)?;
+
+        if !studying_state.is_done {
+            if let Some(forecast_text) = forecast_line(studying_state.scheduler.as_ref(), translations) {
+                hint_font_manager.draw_single_line(canvas, &forecast_text, margin as i32, text_y)?;
+            }
+        }
     }
-    
-    sprite.draw(canvas)?;
+
+    sprite.draw(canvas, theme)?;
     let content_viewport = Rect::new(0, 25, 512, 305);
     canvas.set_clip_rect(Some(content_viewport));
 
-    if !studying_state.is_answer_revealed {
+    let mut content_bottom = if !studying_state.is_answer_revealed {
+        let mut y_pos = 40;
         let layout_to_draw = if studying_state.show_ruby_text { &studying_state.front_layout_ruby } else { &studying_state.front_layout_default };
         if let Some(layout) = layout_to_draw {
-            font_manager.draw_layout(canvas, layout, margin as i32, 40, studying_state.show_ruby_text)?;
+            font_manager.draw_layout(canvas, layout, margin as i32, y_pos, studying_state.show_ruby_text)?;
+            y_pos += layout.total_height;
         }
+        y_pos
     } else {
         let mut y_pos = 40 - studying_state.scroll_offset;
         let small_front_layout_to_draw = if studying_state.show_ruby_text { &studying_state.small_front_layout_ruby } else { &studying_state.small_front_layout_default };
@@ -108,7 +215,20 @@ pub fn draw_studying_scene(
         }
         if let Some(layout) = back_layout_to_draw {
             font_manager.draw_layout(canvas, layout, margin as i32, y_pos, studying_state.show_ruby_text)?;
+            y_pos += layout.total_height;
+        }
+        y_pos
+    };
+
+    // Draw any of the current note's images below its text, in field order,
+    // stacked vertically and scrolling with the rest of the revealed body.
+    for image_path in &studying_state.current_images {
+        content_bottom += IMAGE_SPACING;
+        if let Some(texture) = media_cache.get_or_load(image_path) {
+            let rect = Rect::new(margin as i32, content_bottom, IMAGE_DRAW_SIZE as u32, IMAGE_DRAW_SIZE as u32);
+            canvas.copy(texture, None, Some(rect))?;
         }
+        content_bottom += IMAGE_DRAW_SIZE;
     }
 
     if studying_state.is_done {
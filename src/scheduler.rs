@@ -2,9 +2,41 @@
 // Contains the logic for the spaced repetition system.
 
 use crate::deck::{Card, Deck, Note};
+use crate::storage::DatabaseManager;
 use rand::seq::SliceRandom;
 use rand::thread_rng;
 use std::collections::HashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Wall-clock time assumed per card review when turning a re-queued card's
+/// position in the session queue into an estimated return time for
+/// `Scheduler::next_due_in`. The session has no real per-card timers, so this
+/// is a rough forecast rather than a measured one.
+const ASSUMED_SECONDS_PER_CARD: u64 = 12;
+
+/// Default cap on how many never-before-seen cards a session will introduce.
+const DEFAULT_NEW_CARDS_PER_DAY: usize = 20;
+/// Default cap on how many due review cards a session will serve.
+const DEFAULT_REVIEWS_PER_DAY: usize = 200;
+
+/// Traditional SM-2 multiplier applied on top of the ease factor when a card
+/// is rated Easy, on the theory that an easy review should grow the interval
+/// faster than a plain Good one does.
+const EASY_BONUS: f32 = 1.3;
+/// Smallest interval (in days) an Easy review can produce, so a brand-new
+/// card (interval 0) still gets pushed meaningfully into the future.
+const MIN_EASY_INTERVAL: u32 = 4;
+
+/// The current day, expressed as days since the Unix epoch. This is the
+/// `today` value callers should pass to `Scheduler::new`/`answer_card` so
+/// `Card::due` can be compared against it.
+pub fn current_day_number() -> i64 {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    (secs / 86_400) as i64
+}
 
 /// Represents the user's rating for a card.
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -15,17 +47,182 @@ pub enum Rating {
     Easy,
 }
 
+impl Rating {
+    /// The FSRS grade G used in its weight formulas: Again=1, Hard=2, Good=3, Easy=4.
+    fn grade(self) -> f64 {
+        match self {
+            Rating::Again => 1.0,
+            Rating::Hard => 2.0,
+            Rating::Good => 3.0,
+            Rating::Easy => 4.0,
+        }
+    }
+}
+
+/// Counts of cards still waiting in the session queue, broken out by the same
+/// new/learning/review buckets `build_due_queue` sorts into (a "learning"
+/// card is one that was failed and re-queued this session, not yet a mature
+/// review). Used for the study scene's "N learning · M review left" forecast.
+pub struct QueueCounts {
+    pub new: usize,
+    pub learning: usize,
+    pub review: usize,
+}
+
 /// A trait defining the core behavior of any scheduling algorithm.
 pub trait Scheduler {
-    fn new(deck: Deck) -> Self where Self: Sized;
+    /// `today` is the day number (see `current_day_number`) the session is starting on;
+    /// only cards already due by then are loaded into the session queue.
+    fn new(deck: Deck, today: i64) -> Self where Self: Sized;
+    /// Like `new`, but overrides the default cap on never-before-seen cards
+    /// (`Settings::new_cards_per_day`) instead of using `DEFAULT_NEW_CARDS_PER_DAY`.
+    fn new_with_new_card_limit(deck: Deck, today: i64, new_cards_per_day: usize) -> Self where Self: Sized;
     fn next_card(&mut self) -> Option<Card>;
-    fn answer_card(&mut self, card_id: i64, rating: Rating);
+    /// Grades a card and returns its updated state, or `None` if `card_id` isn't known.
+    /// `today` re-anchors the due date the new interval is computed from.
+    fn answer_card(&mut self, card_id: i64, rating: Rating, today: i64) -> Option<Card>;
     fn get_note(&self, note_id: i64) -> Option<&Note>;
     fn reviews_complete(&self) -> usize;
     fn total_session_cards(&self) -> usize;
     fn hard_cards(&self) -> &[i64];
     fn rewind_last_answer(&mut self) -> Option<Card>;
     fn add_card_to_front(&mut self, card_id: i64);
+    /// Every card currently tracked by the scheduler, scheduling state included.
+    fn all_cards(&self) -> Vec<Card>;
+    /// New/learning/review counts still waiting in the session queue.
+    fn queue_counts(&self) -> QueueCounts;
+    /// Estimated time until the soonest re-queued ("learning") card in the
+    /// session becomes the active card again, or `None` if none is queued.
+    fn next_due_in(&self) -> Option<Duration>;
+    /// How many `Rating::Again` grades have been given this session.
+    fn again_count(&self) -> usize;
+
+    /// The soonest day (relative to `today`; 0 means the deck still has cards
+    /// due today that this session didn't get to) this deck will next have a
+    /// card due, or `None` if it has no cards at all.
+    fn next_due_day(&self, today: i64) -> Option<i64> {
+        self.all_cards().into_iter().map(|card| (card.due - today).max(0)).min()
+    }
+
+    /// Loads `deck` with any persisted scheduling state in `db` overlaid onto its
+    /// cards, so a session resumes exactly where the previous one left off.
+    fn new_from_db(mut deck: Deck, db: &DatabaseManager, today: i64) -> Self
+    where
+        Self: Sized,
+    {
+        apply_persisted_state(&mut deck, db);
+        Self::new(deck, today)
+    }
+
+    /// Like `new_from_db`, but with the new-card cap overridden by
+    /// `Settings::new_cards_per_day` rather than `DEFAULT_NEW_CARDS_PER_DAY`.
+    fn new_from_db_with_limit(mut deck: Deck, db: &DatabaseManager, today: i64, new_cards_per_day: usize) -> Self
+    where
+        Self: Sized,
+    {
+        apply_persisted_state(&mut deck, db);
+        Self::new_with_new_card_limit(deck, today, new_cards_per_day)
+    }
+
+    /// Persists every tracked card's current scheduling state.
+    fn save(&self, db: &DatabaseManager) -> Result<(), String> {
+        for card in self.all_cards() {
+            db.update_card_state(&card).map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    }
+}
+
+/// Overlays whatever scheduling state `db` has persisted for `deck`'s cards
+/// onto them in place, shared by `Scheduler::new_from_db` and
+/// `Scheduler::new_from_db_with_limit`.
+fn apply_persisted_state(deck: &mut Deck, db: &DatabaseManager) {
+    if let Ok(states) = db.load_card_states() {
+        for card in deck.cards.iter_mut() {
+            if let Some(state) = states.get(&card.id) {
+                card.due = state.due;
+                card.interval = state.interval;
+                card.ease_factor = state.ease_factor;
+                card.lapses = state.lapses;
+                card.stability = state.stability;
+                card.difficulty = state.difficulty;
+            }
+        }
+    }
+}
+
+/// Splits `cards_map`'s due cards into a session queue, capping new and review
+/// cards at `new_per_day`/`reviews_per_day` and ordering them the same way a
+/// card's scheduler already orders a full deck (sorted in tests, shuffled otherwise).
+fn build_due_queue(
+    cards_map: &HashMap<i64, Card>,
+    today: i64,
+    new_per_day: usize,
+    reviews_per_day: usize,
+) -> Vec<i64> {
+    let mut new_cards: Vec<i64> = Vec::new();
+    let mut review_cards: Vec<i64> = Vec::new();
+
+    for card in cards_map.values() {
+        if card.due > today {
+            continue;
+        }
+        if card.interval == 0 && card.lapses == 0 {
+            new_cards.push(card.id);
+        } else {
+            review_cards.push(card.id);
+        }
+    }
+
+    if cfg!(test) {
+        new_cards.sort_unstable();
+        review_cards.sort_unstable();
+    } else {
+        new_cards.shuffle(&mut thread_rng());
+        review_cards.shuffle(&mut thread_rng());
+    }
+    new_cards.truncate(new_per_day);
+    review_cards.truncate(reviews_per_day);
+
+    // Review cards go after new cards; since `next_card` pops from the end,
+    // review cards are served first and new cards follow once the review
+    // backlog is cleared.
+    let mut queue = new_cards;
+    queue.extend(review_cards);
+    if cfg!(test) {
+        queue.sort_unstable();
+    }
+    queue
+}
+
+/// Shared `queue_counts` implementation for both schedulers: buckets whatever
+/// is left in `review_queue` the same way `build_due_queue` classified it.
+fn queue_counts_from(review_queue: &[i64], cards: &HashMap<i64, Card>) -> QueueCounts {
+    let mut counts = QueueCounts { new: 0, learning: 0, review: 0 };
+    for id in review_queue {
+        if let Some(card) = cards.get(id) {
+            if card.interval == 0 && card.lapses == 0 {
+                counts.new += 1;
+            } else if card.interval == 0 {
+                counts.learning += 1;
+            } else {
+                counts.review += 1;
+            }
+        }
+    }
+    counts
+}
+
+/// Shared `next_due_in` implementation for both schedulers: walks the queue
+/// from the top (the next card to be served) outward, looking for the
+/// soonest re-queued ("learning") card, and turns its distance from the top
+/// into an estimate via `ASSUMED_SECONDS_PER_CARD`.
+fn next_due_in_from(review_queue: &[i64], cards: &HashMap<i64, Card>) -> Option<Duration> {
+    review_queue
+        .iter()
+        .rev()
+        .position(|id| cards.get(id).map_or(false, |card| card.interval == 0 && card.lapses > 0))
+        .map(|position| Duration::from_secs(position as u64 * ASSUMED_SECONDS_PER_CARD))
 }
 
 /// Implementation of the SM-2 algorithm.
@@ -36,21 +233,18 @@ pub struct Sm2Scheduler {
     session_total: usize,
     session_reviews_complete: usize,
     hard_cards_this_session: Vec<i64>,
+    again_count_this_session: usize,
     last_answer: Option<(i64, Rating, Card)>, // Store a clone of the card state before modification
 }
 
 impl Scheduler for Sm2Scheduler {
-    fn new(deck: Deck) -> Self {
+    fn new(deck: Deck, today: i64) -> Self {
+        Self::new_with_new_card_limit(deck, today, DEFAULT_NEW_CARDS_PER_DAY)
+    }
+
+    fn new_with_new_card_limit(deck: Deck, today: i64, new_cards_per_day: usize) -> Self {
         let cards_map: HashMap<i64, Card> = deck.cards.into_iter().map(|c| (c.id, c)).collect();
-        let mut review_queue: Vec<i64> = cards_map.keys().cloned().collect();
-        
-        if cfg!(test) {
-            // Sort ascending for predictable test order. .pop() will take from the end.
-            review_queue.sort_unstable(); 
-        } else {
-            review_queue.shuffle(&mut thread_rng());
-        }
-        
+        let review_queue = build_due_queue(&cards_map, today, new_cards_per_day, DEFAULT_REVIEWS_PER_DAY);
         let session_total = review_queue.len();
 
         Sm2Scheduler {
@@ -60,6 +254,7 @@ impl Scheduler for Sm2Scheduler {
             session_total,
             session_reviews_complete: 0,
             hard_cards_this_session: Vec::new(),
+            again_count_this_session: 0,
             last_answer: None,
         }
     }
@@ -67,32 +262,38 @@ impl Scheduler for Sm2Scheduler {
     fn next_card(&mut self) -> Option<Card> {
         self.review_queue.pop().and_then(|id| self.cards.get(&id).cloned())
     }
-    
+
     fn add_card_to_front(&mut self, card_id: i64) {
         // Pushing to the end of the vec makes it the next item for .pop()
         self.review_queue.push(card_id);
     }
 
-    fn answer_card(&mut self, card_id: i64, rating: Rating) {
-        let card = self.cards.get_mut(&card_id).unwrap();
-        
+    fn answer_card(&mut self, card_id: i64, rating: Rating, today: i64) -> Option<Card> {
+        let card = self.cards.get_mut(&card_id)?;
+
         self.last_answer = Some((card_id, rating, card.clone()));
 
         match rating {
             Rating::Again => {
+                self.again_count_this_session += 1;
                 card.lapses += 1;
                 card.ease_factor = (card.ease_factor as i32 - 200).max(1300) as u32;
                 card.interval = 0;
-                
+                card.due = today;
+
                 let cooldown_distance = 5_u32.saturating_sub(card.lapses).max(2) as usize;
                 let insertion_point = self.review_queue.len().saturating_sub(cooldown_distance);
                 self.review_queue.insert(insertion_point, card.id);
             }
             _ => { // Hard, Good, or Easy
                 self.session_reviews_complete += 1;
-               
+
                 match rating {
-                    Rating::Good => { /* ... interval logic ... */ }
+                    Rating::Good => {
+                        let ease = card.ease_factor as f32 / 1000.0;
+                        let new_interval = (card.interval as f32 * ease).round() as u32;
+                        card.interval = new_interval.max(card.interval + 1);
+                    }
                     Rating::Hard => {
                         if !self.hard_cards_this_session.contains(&card_id) {
                             self.hard_cards_this_session.push(card_id);
@@ -101,11 +302,19 @@ impl Scheduler for Sm2Scheduler {
                         let new_interval = (card.interval as f32 * 1.2).round() as u32;
                         card.interval = new_interval.max(card.interval + 1);
                     }
-                    Rating::Easy => { /* ... interval logic ... */ }
+                    Rating::Easy => {
+                        card.ease_factor += 150;
+                        let ease = card.ease_factor as f32 / 1000.0;
+                        let new_interval = (card.interval as f32 * ease * EASY_BONUS).round() as u32;
+                        card.interval = new_interval.max(MIN_EASY_INTERVAL).max(card.interval + 1);
+                    }
                     Rating::Again => {} // Already handled
                 }
+                card.due = today + card.interval as i64;
             }
         }
+
+        self.cards.get(&card_id).cloned()
     }
 
     fn rewind_last_answer(&mut self) -> Option<Card> {
@@ -118,14 +327,16 @@ impl Scheduler for Sm2Scheduler {
             // Revert state changes.
             if rating != Rating::Again {
                 self.session_reviews_complete = self.session_reviews_complete.saturating_sub(1);
+            } else {
+                self.again_count_this_session = self.again_count_this_session.saturating_sub(1);
             }
             if rating == Rating::Hard {
                 self.hard_cards_this_session.retain(|&id| id != card_id);
             }
-            
+
             // Restore the card to its original state.
             self.cards.insert(card_id, original_card_state);
-            
+
             return self.cards.get(&card_id).cloned();
         }
         None
@@ -135,6 +346,195 @@ impl Scheduler for Sm2Scheduler {
     fn reviews_complete(&self) -> usize { self.session_reviews_complete }
     fn total_session_cards(&self) -> usize { self.session_total }
     fn hard_cards(&self) -> &[i64] { &self.hard_cards_this_session }
+    fn all_cards(&self) -> Vec<Card> { self.cards.values().cloned().collect() }
+    fn again_count(&self) -> usize { self.again_count_this_session }
+
+    fn queue_counts(&self) -> QueueCounts {
+        queue_counts_from(&self.review_queue, &self.cards)
+    }
+
+    fn next_due_in(&self) -> Option<Duration> {
+        next_due_in_from(&self.review_queue, &self.cards)
+    }
+}
+
+/// Tunable weights for the FSRS (Free Spaced Repetition Scheduler) algorithm.
+/// The defaults are the commonly published FSRS-4.5 weights; they can be
+/// overridden later (e.g. once per-user optimization is added) by constructing
+/// this directly instead of via `Default`.
+#[derive(Debug, Clone)]
+pub struct FsrsWeights {
+    pub w: [f64; 17],
+    /// Desired probability (0.0-1.0) of recalling a card when it comes due.
+    pub request_retention: f64,
+}
+
+impl Default for FsrsWeights {
+    fn default() -> Self {
+        FsrsWeights {
+            w: [
+                0.4072, 1.1829, 3.1262, 15.4722, 7.2102, 0.5316, 1.0651, 0.0234,
+                1.616, 0.1544, 1.0824, 1.9813, 0.0953, 0.2975, 2.2042, 0.2407,
+                2.9466,
+            ],
+            request_retention: 0.9,
+        }
+    }
+}
+
+/// Implementation of the FSRS algorithm, tracking per-card stability and
+/// difficulty instead of SM-2's single ease factor.
+pub struct FsrsScheduler {
+    cards: HashMap<i64, Card>,
+    notes: HashMap<i64, Note>,
+    review_queue: Vec<i64>,
+    session_total: usize,
+    session_reviews_complete: usize,
+    hard_cards_this_session: Vec<i64>,
+    again_count_this_session: usize,
+    last_answer: Option<(i64, Rating, Card)>,
+    weights: FsrsWeights,
+}
+
+impl FsrsScheduler {
+    /// The D0 difficulty that an `Easy` first rating would produce, used as the
+    /// mean-reversion target on later reviews.
+    fn d0_easy(w: &[f64; 17]) -> f64 {
+        (w[4] - (w[5] * (Rating::Easy.grade() - 1.0)).exp() + 1.0).clamp(1.0, 10.0)
+    }
+}
+
+impl Scheduler for FsrsScheduler {
+    fn new(deck: Deck, today: i64) -> Self {
+        Self::new_with_new_card_limit(deck, today, DEFAULT_NEW_CARDS_PER_DAY)
+    }
+
+    fn new_with_new_card_limit(deck: Deck, today: i64, new_cards_per_day: usize) -> Self {
+        let cards_map: HashMap<i64, Card> = deck.cards.into_iter().map(|c| (c.id, c)).collect();
+        let review_queue = build_due_queue(&cards_map, today, new_cards_per_day, DEFAULT_REVIEWS_PER_DAY);
+        let session_total = review_queue.len();
+
+        FsrsScheduler {
+            cards: cards_map,
+            notes: deck.notes,
+            review_queue,
+            session_total,
+            session_reviews_complete: 0,
+            hard_cards_this_session: Vec::new(),
+            again_count_this_session: 0,
+            last_answer: None,
+            weights: FsrsWeights::default(),
+        }
+    }
+
+    fn next_card(&mut self) -> Option<Card> {
+        self.review_queue.pop().and_then(|id| self.cards.get(&id).cloned())
+    }
+
+    fn add_card_to_front(&mut self, card_id: i64) {
+        self.review_queue.push(card_id);
+    }
+
+    fn answer_card(&mut self, card_id: i64, rating: Rating, today: i64) -> Option<Card> {
+        let w = self.weights.w;
+        let card = self.cards.get_mut(&card_id)?;
+        self.last_answer = Some((card_id, rating, card.clone()));
+
+        let g = rating.grade();
+        match (card.stability, card.difficulty) {
+            (Some(s), Some(d)) => {
+                // Elapsed days since the card was last scheduled.
+                let t = card.interval.max(1) as f64;
+                let r = (1.0 + (19.0 / 81.0) * t / s).powf(-0.5);
+
+                let d_prime = w[6] * (-(g - 3.0)) + d;
+                let d_double_prime = (w[7] * Self::d0_easy(&w) + (1.0 - w[7]) * d_prime).clamp(1.0, 10.0);
+
+                let new_stability = if rating == Rating::Again {
+                    w[11] * d.powf(-w[12]) * ((s + 1.0).powf(w[13]) - 1.0) * (w[14] * (1.0 - r)).exp()
+                } else {
+                    let hard_penalty = if rating == Rating::Hard { w[15] } else { 1.0 };
+                    let easy_bonus = if rating == Rating::Easy { w[16] } else { 1.0 };
+                    s * (1.0
+                        + w[8].exp()
+                            * (11.0 - d)
+                            * s.powf(-w[9])
+                            * ((w[10] * (1.0 - r)).exp() - 1.0)
+                            * hard_penalty
+                            * easy_bonus)
+                };
+
+                card.stability = Some(new_stability);
+                card.difficulty = Some(d_double_prime);
+            }
+            _ => {
+                // First rating for this card: seed stability/difficulty from the weights.
+                card.stability = Some(w[(g as usize) - 1]);
+                card.difficulty = Some((w[4] - (w[5] * (g - 1.0)).exp() + 1.0).clamp(1.0, 10.0));
+            }
+        }
+
+        match rating {
+            Rating::Again => {
+                self.again_count_this_session += 1;
+                card.lapses += 1;
+                card.interval = 0;
+                card.due = today;
+
+                let cooldown_distance = 5_u32.saturating_sub(card.lapses).max(2) as usize;
+                let insertion_point = self.review_queue.len().saturating_sub(cooldown_distance);
+                self.review_queue.insert(insertion_point, card.id);
+            }
+            _ => {
+                self.session_reviews_complete += 1;
+                if rating == Rating::Hard && !self.hard_cards_this_session.contains(&card_id) {
+                    self.hard_cards_this_session.push(card_id);
+                }
+
+                let stability = card.stability.unwrap_or(1.0);
+                let interval = (stability / (19.0 / 81.0))
+                    * (self.weights.request_retention.powf(1.0 / -0.5) - 1.0);
+                card.interval = (interval.round() as u32).max(1);
+                card.due = today + card.interval as i64;
+            }
+        }
+
+        self.cards.get(&card_id).cloned()
+    }
+
+    fn rewind_last_answer(&mut self) -> Option<Card> {
+        if let Some((card_id, rating, original_card_state)) = self.last_answer.take() {
+            self.review_queue.retain(|&id| id != card_id);
+
+            if rating != Rating::Again {
+                self.session_reviews_complete = self.session_reviews_complete.saturating_sub(1);
+            } else {
+                self.again_count_this_session = self.again_count_this_session.saturating_sub(1);
+            }
+            if rating == Rating::Hard {
+                self.hard_cards_this_session.retain(|&id| id != card_id);
+            }
+
+            self.cards.insert(card_id, original_card_state);
+            return self.cards.get(&card_id).cloned();
+        }
+        None
+    }
+
+    fn get_note(&self, note_id: i64) -> Option<&Note> { self.notes.get(&note_id) }
+    fn reviews_complete(&self) -> usize { self.session_reviews_complete }
+    fn total_session_cards(&self) -> usize { self.session_total }
+    fn hard_cards(&self) -> &[i64] { &self.hard_cards_this_session }
+    fn all_cards(&self) -> Vec<Card> { self.cards.values().cloned().collect() }
+    fn again_count(&self) -> usize { self.again_count_this_session }
+
+    fn queue_counts(&self) -> QueueCounts {
+        queue_counts_from(&self.review_queue, &self.cards)
+    }
+
+    fn next_due_in(&self) -> Option<Duration> {
+        next_due_in_from(&self.review_queue, &self.cards)
+    }
 }
 
 #[cfg(test)]
@@ -147,15 +547,15 @@ mod tests {
         for i in 0..num_cards {
             let card_id = i as i64;
             let note_id = i as i64;
-            cards.push(Card { id: card_id, note_id, due: 0, interval: 0, ease_factor: 2500, lapses: 0 });
-            notes.insert(note_id, Note { id: note_id, fields: vec![format!("Front {}", i), format!("Back {}", i)] });
+            cards.push(Card { id: card_id, note_id, due: 0, interval: 0, ease_factor: 2500, lapses: 0, stability: None, difficulty: None });
+            notes.insert(note_id, Note { id: note_id, fields: vec![format!("Front {}", i), format!("Back {}", i)], media: Vec::new() });
         }
         Deck { cards, notes }
     }
 
     #[test]
     fn test_initialization() {
-        let mut scheduler = Sm2Scheduler::new(create_test_deck(10));
+        let mut scheduler = Sm2Scheduler::new(create_test_deck(10), 0);
         assert_eq!(scheduler.total_session_cards(), 10);
         assert_eq!(scheduler.reviews_complete(), 0);
         // Test that pop returns highest ID first because of test-only sort
@@ -164,23 +564,23 @@ mod tests {
 
     #[test]
     fn test_review_flow() {
-        let mut scheduler = Sm2Scheduler::new(create_test_deck(5));
+        let mut scheduler = Sm2Scheduler::new(create_test_deck(5), 0);
         let card = scheduler.next_card().unwrap();
-        scheduler.answer_card(card.id, Rating::Good);
+        scheduler.answer_card(card.id, Rating::Good, 0);
         assert_eq!(scheduler.reviews_complete(), 1);
         let card = scheduler.next_card().unwrap();
-        scheduler.answer_card(card.id, Rating::Easy);
+        scheduler.answer_card(card.id, Rating::Easy, 0);
         assert_eq!(scheduler.reviews_complete(), 2);
     }
 
     #[test]
     fn test_again_cooldown() {
-        let mut scheduler = Sm2Scheduler::new(create_test_deck(7));
+        let mut scheduler = Sm2Scheduler::new(create_test_deck(7), 0);
         
         let failed_card = scheduler.next_card().unwrap();
         assert_eq!(failed_card.id, 6);
 
-        scheduler.answer_card(failed_card.id, Rating::Again);
+        scheduler.answer_card(failed_card.id, Rating::Again, 0);
         assert_eq!(scheduler.reviews_complete(), 0);
 
         // Pop the next 4 cards from the queue
@@ -195,13 +595,13 @@ mod tests {
 
     #[test]
     fn test_rewind() {
-        let mut scheduler = Sm2Scheduler::new(create_test_deck(5));
+        let mut scheduler = Sm2Scheduler::new(create_test_deck(5), 0);
 
         let card_4 = scheduler.next_card().unwrap(); // id=4
-        scheduler.answer_card(card_4.id, Rating::Good); // reviews=1
+        scheduler.answer_card(card_4.id, Rating::Good, 0); // reviews=1
         
         let card_3 = scheduler.next_card().unwrap(); // id=3
-        scheduler.answer_card(card_3.id, Rating::Hard); // reviews=2, hard_cards=[3]
+        scheduler.answer_card(card_3.id, Rating::Hard, 0); // reviews=2, hard_cards=[3]
         
         assert_eq!(scheduler.reviews_complete(), 2);
         assert_eq!(scheduler.hard_cards(), &[3]);
@@ -226,8 +626,79 @@ mod tests {
         assert_eq!(next.id, 3);
 
         // After answering the rewound card, the next should be the one we held (2)
-        scheduler.answer_card(next.id, Rating::Good);
+        scheduler.answer_card(next.id, Rating::Good, 0);
         let final_card = scheduler.next_card().unwrap();
         assert_eq!(final_card.id, 2);
     }
+
+    #[test]
+    fn test_fsrs_initializes_stability_and_difficulty_on_first_review() {
+        let mut scheduler = FsrsScheduler::new(create_test_deck(3), 0);
+        let card = scheduler.next_card().unwrap();
+        assert!(card.stability.is_none());
+
+        scheduler.answer_card(card.id, Rating::Good, 0);
+        let card = scheduler.cards.get(&card.id).unwrap();
+        assert!(card.stability.unwrap() > 0.0);
+        assert!((1.0..=10.0).contains(&card.difficulty.unwrap()));
+        assert!(card.interval >= 1);
+    }
+
+    #[test]
+    fn test_fsrs_lapses_reset_interval_and_reschedule_stability() {
+        let mut scheduler = FsrsScheduler::new(create_test_deck(3), 0);
+        let card = scheduler.next_card().unwrap();
+
+        scheduler.answer_card(card.id, Rating::Good, 0);
+        let after_good = scheduler.cards.get(&card.id).unwrap().clone();
+
+        scheduler.answer_card(card.id, Rating::Again, 0);
+        let after_lapse = scheduler.cards.get(&card.id).unwrap();
+        assert_eq!(after_lapse.interval, 0);
+        assert_eq!(after_lapse.lapses, 1);
+        assert!(after_lapse.stability.unwrap() != after_good.stability.unwrap());
+    }
+
+    #[test]
+    fn test_not_yet_due_cards_are_excluded_from_the_session() {
+        let mut deck = create_test_deck(5);
+        // Push card #4 a week into the future; the rest stay due today (due: 0).
+        deck.cards[4].due = 7;
+
+        let scheduler = Sm2Scheduler::new(deck, 0);
+        assert_eq!(scheduler.total_session_cards(), 4);
+    }
+
+    #[test]
+    fn test_answer_card_reschedules_due_date_from_today() {
+        let mut scheduler = Sm2Scheduler::new(create_test_deck(3), 10);
+        let card = scheduler.next_card().unwrap();
+
+        scheduler.answer_card(card.id, Rating::Hard, 10);
+        let updated = scheduler.cards.get(&card.id).unwrap();
+        assert_eq!(updated.due, 10 + updated.interval as i64);
+
+        scheduler.answer_card(card.id, Rating::Again, 10);
+        let updated = scheduler.cards.get(&card.id).unwrap();
+        assert_eq!(updated.due, 10);
+    }
+
+    #[test]
+    fn test_good_and_easy_ratings_advance_due_date() {
+        let mut scheduler = Sm2Scheduler::new(create_test_deck(3), 10);
+        let good_card = scheduler.next_card().unwrap();
+
+        scheduler.answer_card(good_card.id, Rating::Good, 10);
+        let updated = scheduler.cards.get(&good_card.id).unwrap();
+        assert!(updated.interval >= 1);
+        assert_eq!(updated.due, 10 + updated.interval as i64);
+        assert!(updated.due > 10);
+
+        let easy_card = scheduler.next_card().unwrap();
+        scheduler.answer_card(easy_card.id, Rating::Easy, 10);
+        let updated = scheduler.cards.get(&easy_card.id).unwrap();
+        assert!(updated.interval >= MIN_EASY_INTERVAL);
+        assert_eq!(updated.due, 10 + updated.interval as i64);
+        assert!(updated.due > 10);
+    }
 }
@@ -0,0 +1,143 @@
+// src/settings.rs
+// Persisted user preferences, adjustable at runtime from the in-study
+// options scene (see `scenes::options`) and reloaded at startup alongside
+// `Config`.
+
+use std::path::Path;
+
+/// Which physical button fires which `Rating` from the revealed-answer
+/// keyboard/controller mapping: `Standard` matches the on-screen hint text
+/// (`A:Good B:Again X:Easy Y:Hard`); `Swapped` mirrors it (A/B and X/Y
+/// traded), for players who find the default awkward.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RatingButtonLayout {
+    Standard,
+    Swapped,
+}
+
+/// User-adjustable preferences, overlaid onto defaults from a `settings.toml`
+/// next to the executable and saved back to the same path on every change.
+pub struct Settings {
+    /// Multiplier applied to each `DeviceProfile` font tier's point size.
+    /// Read once at startup (`FontManager` can't be rebuilt mid-session), so
+    /// changes take effect the next time the app launches.
+    pub font_scale: f32,
+    /// 0.0-1.0, applied to all SFX mix channels immediately on change.
+    pub sfx_volume: f32,
+    /// 0.0-1.0, applied to the jukebox's background music immediately on change.
+    pub bgm_volume: f32,
+    pub show_ruby_by_default: bool,
+    pub new_cards_per_day: u32,
+    pub rating_button_layout: RatingButtonLayout,
+    /// Master on/off for `SoundManager::play_sfx`, toggled from the settings
+    /// screen. Volume sliders stay untouched so re-enabling restores the
+    /// previous level.
+    pub sfx_enabled: bool,
+    /// Whether navigating/activating the main menu plays its click/open SFX,
+    /// checked directly in `handle_main_menu_input`.
+    pub menu_sound_enabled: bool,
+    /// Whether losing window focus pauses the fixed-timestep update loop,
+    /// checked in `main::run`.
+    pub pause_on_focus_loss: bool,
+    /// Active UI language, one of `Translations`'s bundled tables ("en" or
+    /// "ja"). Toggled from the settings screen, which also calls
+    /// `Translations::set_language` immediately so the switch takes effect
+    /// without a restart.
+    pub language: String,
+}
+
+impl Settings {
+    pub fn default_settings() -> Self {
+        Self {
+            font_scale: 1.0,
+            sfx_volume: 1.0,
+            bgm_volume: 1.0,
+            show_ruby_by_default: false,
+            new_cards_per_day: 20,
+            rating_button_layout: RatingButtonLayout::Standard,
+            sfx_enabled: true,
+            menu_sound_enabled: true,
+            pause_on_focus_loss: true,
+            language: "en".to_string(),
+        }
+    }
+
+    /// Starts from [`Settings::default_settings`] and overrides it with
+    /// whatever keys are present in `path`. Missing or unparseable files are
+    /// silently ignored and leave the defaults in place.
+    pub fn load(path: &Path) -> Self {
+        let mut settings = Self::default_settings();
+        if let Ok(contents) = std::fs::read_to_string(path) {
+            if let Err(e) = settings.apply_overrides(&contents) {
+                eprintln!("ignoring invalid settings at {:?}: {}", path, e);
+            }
+        }
+        settings
+    }
+
+    fn apply_overrides(&mut self, contents: &str) -> Result<(), String> {
+        let value: toml::Value = contents.parse().map_err(|e: toml::de::Error| e.to_string())?;
+        let table = value.as_table().ok_or("settings file must be a table")?;
+
+        if let Some(v) = table.get("font_scale").and_then(|v| v.as_float()) {
+            self.font_scale = v as f32;
+        }
+        if let Some(v) = table.get("sfx_volume").and_then(|v| v.as_float()) {
+            self.sfx_volume = v as f32;
+        }
+        if let Some(v) = table.get("bgm_volume").and_then(|v| v.as_float()) {
+            self.bgm_volume = v as f32;
+        }
+        if let Some(v) = table.get("show_ruby_by_default").and_then(|v| v.as_bool()) {
+            self.show_ruby_by_default = v;
+        }
+        if let Some(v) = table.get("new_cards_per_day").and_then(|v| v.as_integer()) {
+            self.new_cards_per_day = v.max(0) as u32;
+        }
+        if let Some(v) = table.get("rating_button_layout").and_then(|v| v.as_str()) {
+            self.rating_button_layout = match v {
+                "swapped" => RatingButtonLayout::Swapped,
+                _ => RatingButtonLayout::Standard,
+            };
+        }
+        if let Some(v) = table.get("sfx_enabled").and_then(|v| v.as_bool()) {
+            self.sfx_enabled = v;
+        }
+        if let Some(v) = table.get("menu_sound_enabled").and_then(|v| v.as_bool()) {
+            self.menu_sound_enabled = v;
+        }
+        if let Some(v) = table.get("pause_on_focus_loss").and_then(|v| v.as_bool()) {
+            self.pause_on_focus_loss = v;
+        }
+        if let Some(v) = table.get("language").and_then(|v| v.as_str()) {
+            self.language = match v {
+                "ja" => "ja".to_string(),
+                _ => "en".to_string(),
+            };
+        }
+        Ok(())
+    }
+
+    /// Writes every field back out to `path` as a flat TOML table, overwriting
+    /// whatever was there. Called after every adjustment in the options scene
+    /// so a crash doesn't lose the player's preferences.
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let mut table = toml::value::Table::new();
+        table.insert("font_scale".to_string(), toml::Value::Float(self.font_scale as f64));
+        table.insert("sfx_volume".to_string(), toml::Value::Float(self.sfx_volume as f64));
+        table.insert("bgm_volume".to_string(), toml::Value::Float(self.bgm_volume as f64));
+        table.insert("show_ruby_by_default".to_string(), toml::Value::Boolean(self.show_ruby_by_default));
+        table.insert("new_cards_per_day".to_string(), toml::Value::Integer(self.new_cards_per_day as i64));
+        let layout = match self.rating_button_layout {
+            RatingButtonLayout::Standard => "standard",
+            RatingButtonLayout::Swapped => "swapped",
+        };
+        table.insert("rating_button_layout".to_string(), toml::Value::String(layout.to_string()));
+        table.insert("sfx_enabled".to_string(), toml::Value::Boolean(self.sfx_enabled));
+        table.insert("menu_sound_enabled".to_string(), toml::Value::Boolean(self.menu_sound_enabled));
+        table.insert("pause_on_focus_loss".to_string(), toml::Value::Boolean(self.pause_on_focus_loss));
+        table.insert("language".to_string(), toml::Value::String(self.language.clone()));
+
+        std::fs::write(path, toml::Value::Table(table).to_string()).map_err(|e| e.to_string())
+    }
+}
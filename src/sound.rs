@@ -0,0 +1,150 @@
+// src/sound.rs
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use sdl2::mixer::{self, Channel, Chunk, Music};
+
+use crate::config::Config;
+
+/// SDL_mixer's channel/music volume range is 0-128, not 0.0-1.0.
+const MIXER_MAX_VOLUME: f32 = 128.0;
+
+/// One-shot sound effects, each backed by a `Chunk` loaded once at startup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Sfx {
+    UpDown,
+    Open,
+    CardShuffle,
+}
+
+/// Looping background tracks tied to a particular scene, distinct from the
+/// jukebox's free-choice playlist (`scenes::jukebox`), which loops an
+/// arbitrary file from `music_directory` by explicit user choice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BgmTrack {
+    MainMenu,
+    Studying,
+}
+
+impl BgmTrack {
+    fn filename(self) -> &'static str {
+        match self {
+            BgmTrack::MainMenu => "menu-theme.ogg",
+            BgmTrack::Studying => "study-loop.ogg",
+        }
+    }
+}
+
+/// Owns every loaded SFX chunk plus whatever background track is currently
+/// looping (a fixed per-scene theme or a jukebox pick), so every scene
+/// triggers audio through one API instead of reaching into SDL2 mixer types
+/// directly.
+pub struct SoundManager {
+    chunks: HashMap<Sfx, Chunk>,
+    music_directory: PathBuf,
+    _mixer_ctx: mixer::Sdl2MixerContext,
+    /// Kept alive here (rather than on e.g. `JukeboxState`) so a track isn't
+    /// dropped with the screen that started it; a scene with its own
+    /// `BgmTrack` still replaces it through `play_bgm`.
+    now_playing: Option<Music<'static>>,
+    now_playing_name: Option<String>,
+    /// Which fixed `BgmTrack` is currently looping via `play_bgm`, if any, so
+    /// repeated per-frame calls (e.g. from `update_state` on every tick of
+    /// `GameState::MainMenu`) don't restart the track from the beginning.
+    current_track: Option<BgmTrack>,
+    /// `BgmTrack`s whose file failed to load, so a missing/corrupt `.ogg` is
+    /// only attempted once instead of being retried by every one of
+    /// `play_bgm`'s per-frame calls (same fix as `MediaCache::get_or_load`).
+    failed_tracks: HashSet<BgmTrack>,
+    /// Mirrors `Settings::sfx_enabled`; `play_sfx` is a no-op while this is
+    /// `false` instead of every call site checking the setting itself.
+    sfx_enabled: bool,
+}
+
+impl SoundManager {
+    pub fn new(config: &Config, mixer_ctx: mixer::Sdl2MixerContext) -> Result<Self, String> {
+        let mut chunks = HashMap::new();
+        chunks.insert(Sfx::UpDown, Chunk::from_file(config.sfx_directory.join("click.wav"))?);
+        chunks.insert(Sfx::Open, Chunk::from_file(config.sfx_directory.join("open.wav"))?);
+        chunks.insert(Sfx::CardShuffle, Chunk::from_file(config.sfx_directory.join("card-shuffle.wav"))?);
+        Ok(Self {
+            chunks,
+            music_directory: config.music_directory.clone(),
+            _mixer_ctx: mixer_ctx,
+            now_playing: None,
+            now_playing_name: None,
+            current_track: None,
+            failed_tracks: HashSet::new(),
+            sfx_enabled: true,
+        })
+    }
+
+    /// Plays `sfx` once on a free channel, unless muted via
+    /// `set_sfx_enabled`. A missed click shouldn't interrupt navigation, so
+    /// a channel-allocation failure is silently dropped too.
+    pub fn play_sfx(&self, sfx: Sfx) {
+        if !self.sfx_enabled {
+            return;
+        }
+        if let Some(chunk) = self.chunks.get(&sfx) {
+            let _ = Channel::all().play(chunk, 0);
+        }
+    }
+
+    /// Master on/off for `play_sfx`, set from `Settings::sfx_enabled`.
+    pub fn set_sfx_enabled(&mut self, enabled: bool) {
+        self.sfx_enabled = enabled;
+    }
+
+    /// Starts `track` looping from `music_directory`, unless it's already the
+    /// one playing. Safe to call every tick a scene is active. A missing
+    /// track file just leaves nothing playing, and is not retried on
+    /// subsequent calls.
+    pub fn play_bgm(&mut self, track: BgmTrack) {
+        if self.current_track == Some(track) || self.failed_tracks.contains(&track) {
+            return;
+        }
+        let path = self.music_directory.join(track.filename());
+        if self.play_bgm_file(&path, None).is_ok() {
+            self.current_track = Some(track);
+        } else {
+            self.failed_tracks.insert(track);
+        }
+    }
+
+    /// Starts an arbitrary track looping (used by the jukebox's free-choice
+    /// playlist), replacing whatever was already playing. Labels
+    /// `now_playing_name` with `display_name`, falling back to the file's
+    /// stem if none is given.
+    pub fn play_bgm_file(&mut self, path: &Path, display_name: Option<String>) -> Result<(), String> {
+        let music = Music::from_file(path)?;
+        // Negative loop count repeats the track indefinitely.
+        music.play(-1)?;
+        self.now_playing_name = Some(display_name.unwrap_or_else(|| {
+            path.file_stem().and_then(|s| s.to_str()).unwrap_or("unknown track").replace('_', " ")
+        }));
+        self.now_playing = Some(music);
+        self.current_track = None;
+        Ok(())
+    }
+
+    pub fn stop_bgm(&mut self) {
+        Music::halt();
+        self.now_playing = None;
+        self.now_playing_name = None;
+        self.current_track = None;
+    }
+
+    pub fn now_playing_name(&self) -> Option<&str> {
+        self.now_playing_name.as_deref()
+    }
+
+    pub fn set_sfx_volume(&self, volume: f32) {
+        Channel::all().set_volume((volume.clamp(0.0, 1.0) * MIXER_MAX_VOLUME) as i32);
+    }
+
+    pub fn set_music_volume(&self, volume: f32) {
+        Music::set_volume((volume.clamp(0.0, 1.0) * MIXER_MAX_VOLUME) as i32);
+    }
+}
@@ -1,20 +1,30 @@
 // src/state.rs
 
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::mpsc::Receiver;
 
 use crate::config::Config;
 use crate::deck::Deck;
+use crate::i18n::Translations;
+use crate::scenes::controls_menu::ControlsMenuState;
 use crate::scenes::deck_selection::DeckSelectionState;
 use crate::scenes::main_menu::MainMenuState;
+use crate::scenes::options::OptionsState;
+use crate::scenes::settings::SettingsMenuState;
 use crate::scenes::studying::StudyingState;
+use crate::settings::Settings;
 use crate::ui::font::TextLayout;
-use crate::ui::{CanvasManager, FontManager, sprite::Sprite};
+use crate::ui::{CanvasManager, FontManager, MediaCache, sprite::Sprite};
 use sdl2::controller::{GameController};
+use sdl2::GameControllerSubsystem;
+use sdl2::controller::Axis as CtrlAxis;
 use sdl2::controller::Button as CtrlBtn;
 use sdl2::keyboard::Keycode;
 use sdl2::event::Event;
-use sdl2::mixer::{self, Chunk};
+use crate::input::ControllerType;
+use crate::scenes::jukebox::JukeboxState;
+use crate::sound::SoundManager;
 
 
 /// Holds metadata about a single deck, used for selection screens.
@@ -23,6 +33,16 @@ pub struct DeckMetadata {
     pub id: String,
     pub name: String,
     pub path: PathBuf,
+    /// Total cards in the deck, read from the .apkg's `cards` table by
+    /// `deck::loader::read_deck_summary` without fully loading the deck.
+    pub total_count: usize,
+    /// Cards never yet studied (`ivl == 0 && lapses == 0`), matching the
+    /// "new" classification `scheduler::build_due_queue` uses once the deck
+    /// is actually opened.
+    pub new_count: usize,
+    /// Cards with a scheduled review on or before today, matching the
+    /// scheduler's own `due <= today` comparison.
+    pub due_count: usize,
 }
 
 /// Messages sent from the deck loading thread to the main thread.
@@ -34,6 +54,8 @@ pub enum LoaderMessage {
 /// Represents the current screen or state of the application.
 pub enum GameState<'a> {
     MainMenu(MainMenuState),
+    ControlsMenu(ControlsMenuState),
+    Settings(SettingsMenuState),
     GoToDeckSelection,
     DeckSelection(DeckSelectionState),
     Loading {
@@ -41,29 +63,36 @@ pub enum GameState<'a> {
         loading_layout: TextLayout,
         progress: f32,
         deck_id_to_load: String,
+        deck_path: PathBuf,
     },
     Studying(StudyingState<'a>),
+    GoToJukebox,
+    Jukebox(JukeboxState),
+    /// Pushed from `GameState::Studying` via `BrickButton::Start`; closing it
+    /// restores `OptionsState::return_to` rather than a fixed scene.
+    Options(OptionsState<'a>),
     Error(String),
 }
 
-pub struct Sfx {
-    pub up_down_sound: Chunk,
-    pub open_sound: Chunk,
-    pub mixer_ctx: mixer::Sdl2MixerContext
-}
-
 /// The top-level state for the entire application.
 pub struct AppState<'a> {
     pub game_state: GameState<'a>,
     pub available_decks: Vec<DeckMetadata>,
     pub canvas_manager: CanvasManager<'a>,
-    pub font_manager: FontManager<'a, 'a>,
-    pub small_font_manager: FontManager<'a, 'a>,
-    pub hint_font_manager: FontManager<'a, 'a>,
-    pub sprite: Sprite,
+    pub font_manager: FontManager<'a, 'a, 'a>,
+    pub small_font_manager: FontManager<'a, 'a, 'a>,
+    pub hint_font_manager: FontManager<'a, 'a, 'a>,
+    pub media_cache: MediaCache<'a>,
+    pub sprite: Sprite<'a>,
     pub config: Config,
     pub controllers: Vec<GameController>,
-    pub sfx: Sfx
+    /// Kept around (rather than left as a local in `main`) so `run`'s event
+    /// loop can open newly hot-plugged controllers as `ControllerDeviceAdded`
+    /// events arrive.
+    pub gc_subsystem: GameControllerSubsystem,
+    pub sound: SoundManager,
+    pub translations: Translations,
+    pub settings: Settings,
 }
 
 /// All the *buttons* as they’re silkscreened (or logically present) on the Brick.
@@ -89,6 +118,80 @@ pub enum BrickButton {
     Guide,
 }
 
+impl BrickButton {
+    /// The `[buttons.*]` table key this variant is configured under.
+    fn key(self) -> &'static str {
+        match self {
+            BrickButton::A => "a",
+            BrickButton::B => "b",
+            BrickButton::X => "x",
+            BrickButton::Y => "y",
+            BrickButton::DPadUp => "dpad_up",
+            BrickButton::DPadDown => "dpad_down",
+            BrickButton::DPadLeft => "dpad_left",
+            BrickButton::DPadRight => "dpad_right",
+            BrickButton::Power => "power",
+            BrickButton::VolumeUp => "volume_up",
+            BrickButton::VolumeDown => "volume_down",
+            BrickButton::LeftShoulder => "left_shoulder",
+            BrickButton::RightShoulder => "right_shoulder",
+            BrickButton::LeftStick => "left_stick",
+            BrickButton::RightStick => "right_stick",
+            BrickButton::Start => "start",
+            BrickButton::Back => "back",
+            BrickButton::Guide => "guide",
+        }
+    }
+
+    /// A short human-readable name for the controls menu's row labels.
+    pub fn label(self) -> &'static str {
+        match self {
+            BrickButton::A => "A",
+            BrickButton::B => "B",
+            BrickButton::X => "X",
+            BrickButton::Y => "Y",
+            BrickButton::DPadUp => "Up",
+            BrickButton::DPadDown => "Down",
+            BrickButton::DPadLeft => "Left",
+            BrickButton::DPadRight => "Right",
+            BrickButton::Power => "Power",
+            BrickButton::VolumeUp => "Volume Up",
+            BrickButton::VolumeDown => "Volume Down",
+            BrickButton::LeftShoulder => "Left Shoulder",
+            BrickButton::RightShoulder => "Right Shoulder",
+            BrickButton::LeftStick => "Left Stick",
+            BrickButton::RightStick => "Right Stick",
+            BrickButton::Start => "Start",
+            BrickButton::Back => "Back",
+            BrickButton::Guide => "Guide",
+        }
+    }
+
+    fn from_key(key: &str) -> Option<Self> {
+        Some(match key {
+            "a" => BrickButton::A,
+            "b" => BrickButton::B,
+            "x" => BrickButton::X,
+            "y" => BrickButton::Y,
+            "dpad_up" => BrickButton::DPadUp,
+            "dpad_down" => BrickButton::DPadDown,
+            "dpad_left" => BrickButton::DPadLeft,
+            "dpad_right" => BrickButton::DPadRight,
+            "power" => BrickButton::Power,
+            "volume_up" => BrickButton::VolumeUp,
+            "volume_down" => BrickButton::VolumeDown,
+            "left_shoulder" => BrickButton::LeftShoulder,
+            "right_shoulder" => BrickButton::RightShoulder,
+            "left_stick" => BrickButton::LeftStick,
+            "right_stick" => BrickButton::RightStick,
+            "start" => BrickButton::Start,
+            "back" => BrickButton::Back,
+            "guide" => BrickButton::Guide,
+            _ => return None,
+        })
+    }
+}
+
 /// All the *analog axes* you care about.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum BrickAxis {
@@ -96,6 +199,24 @@ pub enum BrickAxis {
     TriggerRight,
 }
 
+impl BrickAxis {
+    /// The `[axes.*]` table key this variant is configured under.
+    fn key(self) -> &'static str {
+        match self {
+            BrickAxis::TriggerLeft => "trigger_left",
+            BrickAxis::TriggerRight => "trigger_right",
+        }
+    }
+
+    fn from_key(key: &str) -> Option<Self> {
+        Some(match key {
+            "trigger_left" => BrickAxis::TriggerLeft,
+            "trigger_right" => BrickAxis::TriggerRight,
+            _ => return None,
+        })
+    }
+}
+
 /// A unified, high‑level event that your app actually handles.
 #[derive(Debug, Copy, Clone)]
 pub enum BrickInput {
@@ -104,98 +225,367 @@ pub enum BrickInput {
     AxisMotion { axis: BrickAxis, value: f32 },
 }
 
-pub fn map_to_brick_input(ev: &Event) -> Option<BrickInput> {
+/// Every SDL identifier that can trigger a single `BrickButton`: a
+/// controller button, a raw joystick button index (for hardware that
+/// doesn't report a standard SDL game controller mapping), or a keycode.
+#[derive(Debug, Clone, Default)]
+struct ButtonBinding {
+    controller_button: Option<CtrlBtn>,
+    joy_button_idx: Option<u8>,
+    keycode: Option<Keycode>,
+}
+
+/// Every SDL identifier that can drive a single `BrickAxis`.
+#[derive(Debug, Clone, Default)]
+struct AxisBinding {
+    controller_axis: Option<CtrlAxis>,
+    joy_axis_idx: Option<u8>,
+}
+
+/// Maps every `BrickButton`/`BrickAxis` to the SDL identifiers that trigger
+/// it. `map_to_brick_input` consults this table instead of hardwiring the
+/// SDL→Brick mapping, since different hardware revisions and clones report
+/// different controller button names and joystick indices.
+///
+/// [`KeyBindings::default_bindings`] reproduces today's behaviour (the A/B
+/// swap and the magic joystick indices); [`KeyBindings::load`] layers a
+/// user's `[buttons]`/`[axes]` overrides on top of it.
+pub struct KeyBindings {
+    buttons: HashMap<BrickButton, ButtonBinding>,
+    axes: HashMap<BrickAxis, AxisBinding>,
+    /// Which D-pad directions the left analog stick is currently holding
+    /// past `STICK_DEADZONE`, so `map_to_brick_input` can edge-trigger a
+    /// single `ButtonDown`/`ButtonUp` per crossing. See `stick_axis_to_dpad`.
+    analog_dpad: AnalogDpadState,
+}
+
+/// Left-stick deadzone past which a tilt counts as a single D-pad-style
+/// press. Only the triggers are exposed as continuous `BrickAxis` values;
+/// the left stick is treated as a 4-way digital pad instead.
+const STICK_DEADZONE: f32 = 0.5;
+
+/// Keycodes the controls menu refuses to rebind onto a face/shoulder button.
+/// `map_to_brick_input` resolves a bound key to its `BrickButton` before any
+/// scene's hardcoded arrow-key fallback runs, so binding over one of these
+/// would shadow menu navigation app-wide with no in-app way to undo it.
+const RESERVED_KEYCODES: [Keycode; 4] =
+    [Keycode::Up, Keycode::Down, Keycode::Return, Keycode::Backspace];
+
+/// Why [`KeyBindings::rebind_key`] refused a capture.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RebindConflict {
+    /// `keycode` drives menu navigation and can never be reassigned.
+    Reserved,
+    /// `keycode` is already bound to this other button; binding it again
+    /// here would leave whichever button loses the `HashMap` lookup in
+    /// `button_for_keycode` unreachable, and which one loses isn't stable
+    /// across runs.
+    AlreadyBound(BrickButton),
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct AnalogDpadState {
+    up: bool,
+    down: bool,
+    left: bool,
+    right: bool,
+}
+
+impl KeyBindings {
+    /// The bindings CardBrick has always shipped with: the A/B swap hack
+    /// and the joystick indices/axes of the reference Brick hardware.
+    pub fn default_bindings() -> Self {
+        let mut buttons = HashMap::new();
+        buttons.insert(BrickButton::A, ButtonBinding { controller_button: Some(CtrlBtn::B), ..Default::default() });
+        buttons.insert(BrickButton::B, ButtonBinding { controller_button: Some(CtrlBtn::A), ..Default::default() });
+        buttons.insert(BrickButton::X, ButtonBinding { controller_button: Some(CtrlBtn::Y), ..Default::default() });
+        buttons.insert(BrickButton::Y, ButtonBinding { controller_button: Some(CtrlBtn::X), ..Default::default() });
+        buttons.insert(BrickButton::DPadUp, ButtonBinding { controller_button: Some(CtrlBtn::DPadUp), ..Default::default() });
+        buttons.insert(BrickButton::DPadDown, ButtonBinding { controller_button: Some(CtrlBtn::DPadDown), ..Default::default() });
+        buttons.insert(BrickButton::DPadLeft, ButtonBinding { controller_button: Some(CtrlBtn::DPadLeft), ..Default::default() });
+        buttons.insert(BrickButton::DPadRight, ButtonBinding { controller_button: Some(CtrlBtn::DPadRight), ..Default::default() });
+        buttons.insert(BrickButton::Start, ButtonBinding { controller_button: Some(CtrlBtn::Start), ..Default::default() });
+        buttons.insert(BrickButton::Back, ButtonBinding { controller_button: Some(CtrlBtn::Back), ..Default::default() });
+        buttons.insert(BrickButton::Guide, ButtonBinding { controller_button: Some(CtrlBtn::Guide), ..Default::default() });
+        buttons.insert(BrickButton::LeftShoulder, ButtonBinding { controller_button: Some(CtrlBtn::LeftShoulder), ..Default::default() });
+        buttons.insert(BrickButton::RightShoulder, ButtonBinding { controller_button: Some(CtrlBtn::RightShoulder), ..Default::default() });
+        buttons.insert(BrickButton::LeftStick, ButtonBinding { controller_button: Some(CtrlBtn::LeftStick), ..Default::default() });
+        buttons.insert(BrickButton::RightStick, ButtonBinding { controller_button: Some(CtrlBtn::RightStick), ..Default::default() });
+        // The Power key comes through as a regular KeyDown/Up.
+        buttons.insert(BrickButton::Power, ButtonBinding { keycode: Some(Keycode::Power), ..Default::default() });
+        // The "volume" buttons arrive as joystick buttons rather than controller buttons.
+        buttons.insert(BrickButton::VolumeUp, ButtonBinding { joy_button_idx: Some(14), ..Default::default() });
+        buttons.insert(BrickButton::VolumeDown, ButtonBinding { joy_button_idx: Some(13), ..Default::default() });
+
+        let mut axes = HashMap::new();
+        // Triggers as analog axes (SDL reports both ControllerAxisMotion and JoyAxisMotion).
+        axes.insert(BrickAxis::TriggerLeft, AxisBinding { controller_axis: Some(CtrlAxis::TriggerLeft), joy_axis_idx: Some(2) });
+        axes.insert(BrickAxis::TriggerRight, AxisBinding { controller_axis: Some(CtrlAxis::TriggerRight), joy_axis_idx: Some(5) });
+
+        Self { buttons, axes, analog_dpad: AnalogDpadState::default() }
+    }
+
+    /// A straightforward mapping for any non-Brick `GameController`: the
+    /// Brick's A/B face-button swap and its magic volume-button joystick
+    /// indices are hardware quirks of the reference device, not something a
+    /// generic USB/Bluetooth gamepad reports. Volume buttons are left
+    /// unbound; a user can add a `[buttons.volume_up]`/`[buttons.volume_down]`
+    /// override for their specific hardware.
+    pub fn generic_bindings() -> Self {
+        let mut buttons = HashMap::new();
+        buttons.insert(BrickButton::A, ButtonBinding { controller_button: Some(CtrlBtn::A), ..Default::default() });
+        buttons.insert(BrickButton::B, ButtonBinding { controller_button: Some(CtrlBtn::B), ..Default::default() });
+        buttons.insert(BrickButton::X, ButtonBinding { controller_button: Some(CtrlBtn::X), ..Default::default() });
+        buttons.insert(BrickButton::Y, ButtonBinding { controller_button: Some(CtrlBtn::Y), ..Default::default() });
+        buttons.insert(BrickButton::DPadUp, ButtonBinding { controller_button: Some(CtrlBtn::DPadUp), ..Default::default() });
+        buttons.insert(BrickButton::DPadDown, ButtonBinding { controller_button: Some(CtrlBtn::DPadDown), ..Default::default() });
+        buttons.insert(BrickButton::DPadLeft, ButtonBinding { controller_button: Some(CtrlBtn::DPadLeft), ..Default::default() });
+        buttons.insert(BrickButton::DPadRight, ButtonBinding { controller_button: Some(CtrlBtn::DPadRight), ..Default::default() });
+        buttons.insert(BrickButton::Start, ButtonBinding { controller_button: Some(CtrlBtn::Start), ..Default::default() });
+        buttons.insert(BrickButton::Back, ButtonBinding { controller_button: Some(CtrlBtn::Back), ..Default::default() });
+        buttons.insert(BrickButton::Guide, ButtonBinding { controller_button: Some(CtrlBtn::Guide), ..Default::default() });
+        buttons.insert(BrickButton::LeftShoulder, ButtonBinding { controller_button: Some(CtrlBtn::LeftShoulder), ..Default::default() });
+        buttons.insert(BrickButton::RightShoulder, ButtonBinding { controller_button: Some(CtrlBtn::RightShoulder), ..Default::default() });
+        buttons.insert(BrickButton::LeftStick, ButtonBinding { controller_button: Some(CtrlBtn::LeftStick), ..Default::default() });
+        buttons.insert(BrickButton::RightStick, ButtonBinding { controller_button: Some(CtrlBtn::RightStick), ..Default::default() });
+        buttons.insert(BrickButton::Power, ButtonBinding { keycode: Some(Keycode::Power), ..Default::default() });
+        buttons.insert(BrickButton::VolumeUp, ButtonBinding::default());
+        buttons.insert(BrickButton::VolumeDown, ButtonBinding::default());
+
+        let mut axes = HashMap::new();
+        axes.insert(BrickAxis::TriggerLeft, AxisBinding { controller_axis: Some(CtrlAxis::TriggerLeft), ..Default::default() });
+        axes.insert(BrickAxis::TriggerRight, AxisBinding { controller_axis: Some(CtrlAxis::TriggerRight), ..Default::default() });
+
+        Self { buttons, axes, analog_dpad: AnalogDpadState::default() }
+    }
+
+    /// Picks [`default_bindings`](Self::default_bindings) or
+    /// [`generic_bindings`](Self::generic_bindings) depending on which kind
+    /// of controller is plugged in.
+    pub fn default_bindings_for(controller_type: ControllerType) -> Self {
+        match controller_type {
+            ControllerType::Brick => Self::default_bindings(),
+            ControllerType::Generic => Self::generic_bindings(),
+        }
+    }
+
+    /// Starts from [`KeyBindings::default_bindings`] and overrides it with
+    /// whatever `[buttons]`/`[axes]` tables are present in `path`, so a user
+    /// on a differently-wired Brick variant can fix their volume/trigger
+    /// buttons without recompiling. Missing or unparseable files are
+    /// silently ignored and leave the defaults in place.
+    pub fn load(path: &Path) -> Self {
+        Self::load_for(path, ControllerType::Brick)
+    }
+
+    /// Like [`load`](Self::load), but starting from `controller_type`'s
+    /// default profile instead of always assuming Brick hardware. Called
+    /// from `main()` once a controller is open and its type known.
+    pub fn load_for(path: &Path, controller_type: ControllerType) -> Self {
+        let mut bindings = Self::default_bindings_for(controller_type);
+        if let Ok(contents) = std::fs::read_to_string(path) {
+            if let Err(e) = bindings.apply_overrides(&contents) {
+                eprintln!("ignoring invalid key bindings at {:?}: {}", path, e);
+            }
+        }
+        bindings
+    }
+
+    /// Binds `button` to `keycode`, leaving any controller/joystick binding
+    /// it already has untouched. Used by the controls menu's "press a key"
+    /// capture mode; callers should `save` afterwards so the remap survives
+    /// a restart. Refuses `keycode` if it's reserved for menu navigation or
+    /// already bound to a different button, so a rebind can never lock the
+    /// player out of the menus or leave a button silently unreachable.
+    pub fn rebind_key(&mut self, button: BrickButton, keycode: Keycode) -> Result<(), RebindConflict> {
+        if RESERVED_KEYCODES.contains(&keycode) {
+            return Err(RebindConflict::Reserved);
+        }
+        if let Some(existing) = self.button_for_keycode(keycode) {
+            if existing != button {
+                return Err(RebindConflict::AlreadyBound(existing));
+            }
+        }
+        self.buttons.entry(button).or_insert_with(ButtonBinding::default).keycode = Some(keycode);
+        Ok(())
+    }
+
+    /// The keycode `button` is currently bound to, if any, for display in
+    /// the controls menu.
+    pub fn keycode_for(&self, button: BrickButton) -> Option<Keycode> {
+        self.buttons.get(&button).and_then(|b| b.keycode)
+    }
+
+    /// Writes every bound button's `keycode` back out to `path` as a
+    /// `[buttons.<name>]` TOML table, mirroring the shape `apply_overrides`
+    /// reads back in. Controller/joystick bindings aren't round-tripped here
+    /// since the controls menu only ever rebinds keycodes.
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let mut buttons_table = toml::value::Table::new();
+        for (button, binding) in &self.buttons {
+            if let Some(keycode) = binding.keycode {
+                let mut entry = toml::value::Table::new();
+                entry.insert("keycode".to_string(), toml::Value::String(keycode.name()));
+                buttons_table.insert(button.key().to_string(), toml::Value::Table(entry));
+            }
+        }
+        let mut root = toml::value::Table::new();
+        root.insert("buttons".to_string(), toml::Value::Table(buttons_table));
+        let contents = toml::Value::Table(root).to_string();
+        std::fs::write(path, contents).map_err(|e| e.to_string())
+    }
+
+    fn apply_overrides(&mut self, contents: &str) -> Result<(), String> {
+        let value: toml::Value = contents.parse().map_err(|e: toml::de::Error| e.to_string())?;
+
+        if let Some(buttons) = value.get("buttons").and_then(|v| v.as_table()) {
+            for (key, binding) in buttons {
+                if let Some(button) = BrickButton::from_key(key) {
+                    let parsed = parse_button_binding(binding);
+                    let entry = self.buttons.entry(button).or_default();
+                    entry.controller_button = parsed.controller_button.or(entry.controller_button);
+                    entry.joy_button_idx = parsed.joy_button_idx.or(entry.joy_button_idx);
+                    entry.keycode = parsed.keycode.or(entry.keycode);
+                }
+            }
+        }
+        if let Some(axes) = value.get("axes").and_then(|v| v.as_table()) {
+            for (key, binding) in axes {
+                if let Some(axis) = BrickAxis::from_key(key) {
+                    self.axes.insert(axis, parse_axis_binding(binding));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn button_for_controller_button(&self, button: CtrlBtn) -> Option<BrickButton> {
+        self.buttons.iter().find(|(_, b)| b.controller_button == Some(button)).map(|(brick, _)| *brick)
+    }
+
+    fn button_for_joy_idx(&self, idx: u8) -> Option<BrickButton> {
+        self.buttons.iter().find(|(_, b)| b.joy_button_idx == Some(idx)).map(|(brick, _)| *brick)
+    }
+
+    fn button_for_keycode(&self, keycode: Keycode) -> Option<BrickButton> {
+        self.buttons.iter().find(|(_, b)| b.keycode == Some(keycode)).map(|(brick, _)| *brick)
+    }
+
+    fn axis_for_controller_axis(&self, axis: CtrlAxis) -> Option<BrickAxis> {
+        self.axes.iter().find(|(_, a)| a.controller_axis == Some(axis)).map(|(brick, _)| *brick)
+    }
+
+    fn axis_for_joy_idx(&self, idx: u8) -> Option<BrickAxis> {
+        self.axes.iter().find(|(_, a)| a.joy_axis_idx == Some(idx)).map(|(brick, _)| *brick)
+    }
+}
+
+fn parse_button_binding(value: &toml::Value) -> ButtonBinding {
+    let table = value.as_table();
+    ButtonBinding {
+        controller_button: table
+            .and_then(|t| t.get("controller_button"))
+            .and_then(|v| v.as_str())
+            .and_then(CtrlBtn::from_string),
+        joy_button_idx: table
+            .and_then(|t| t.get("joy_button"))
+            .and_then(|v| v.as_integer())
+            .map(|i| i as u8),
+        keycode: table
+            .and_then(|t| t.get("keycode"))
+            .and_then(|v| v.as_str())
+            .and_then(Keycode::from_name),
+    }
+}
+
+fn parse_axis_binding(value: &toml::Value) -> AxisBinding {
+    let table = value.as_table();
+    AxisBinding {
+        controller_axis: table
+            .and_then(|t| t.get("controller_axis"))
+            .and_then(|v| v.as_str())
+            .and_then(CtrlAxis::from_string),
+        joy_axis_idx: table
+            .and_then(|t| t.get("joy_axis"))
+            .and_then(|v| v.as_integer())
+            .map(|i| i as u8),
+    }
+}
+
+/// Edge-triggers a D-pad-style `BrickInput` from the left stick crossing
+/// `STICK_DEADZONE` on `axis`, so a stick held past the deadzone behaves
+/// like a held D-pad button instead of flooding `ButtonDown` on every
+/// `ControllerAxisMotion` the stick reports while held there.
+fn stick_axis_to_dpad_input(state: &mut AnalogDpadState, axis: CtrlAxis, value: f32) -> Option<BrickInput> {
+    let (pos_held, neg_held, pos_button, neg_button) = match axis {
+        CtrlAxis::LeftX => (&mut state.right, &mut state.left, BrickButton::DPadRight, BrickButton::DPadLeft),
+        CtrlAxis::LeftY => (&mut state.down, &mut state.up, BrickButton::DPadDown, BrickButton::DPadUp),
+        _ => return None,
+    };
+
+    if *pos_held && value < STICK_DEADZONE {
+        *pos_held = false;
+        return Some(BrickInput::ButtonUp(pos_button));
+    }
+    if *neg_held && value > -STICK_DEADZONE {
+        *neg_held = false;
+        return Some(BrickInput::ButtonUp(neg_button));
+    }
+    if !*pos_held && value >= STICK_DEADZONE {
+        *pos_held = true;
+        return Some(BrickInput::ButtonDown(pos_button));
+    }
+    if !*neg_held && value <= -STICK_DEADZONE {
+        *neg_held = true;
+        return Some(BrickInput::ButtonDown(neg_button));
+    }
+    None
+}
+
+/// Translates a raw SDL event into a `BrickInput` using `bindings` to
+/// resolve which `BrickButton`/`BrickAxis` (if any) it corresponds to.
+/// Takes `bindings` mutably because the left stick's D-pad emulation needs
+/// to remember which directions are already held (see `AnalogDpadState`).
+pub fn map_to_brick_input(ev: &Event, bindings: &mut KeyBindings) -> Option<BrickInput> {
     match ev {
-        // 1) Controller D‑pad & face buttons
         Event::ControllerButtonDown { button, .. } => {
-            let b = match button {
-                CtrlBtn::B        => BrickButton::A,
-                // …but you know it’s really the A button on the Brick.
-                CtrlBtn::A        => BrickButton::B,
-                CtrlBtn::Y        => BrickButton::X,
-                CtrlBtn::X        => BrickButton::Y,
-                CtrlBtn::DPadUp   => BrickButton::DPadUp,
-                CtrlBtn::DPadDown => BrickButton::DPadDown,
-                CtrlBtn::DPadLeft => BrickButton::DPadLeft,
-                CtrlBtn::DPadRight=> BrickButton::DPadRight,
-                CtrlBtn::Start    => BrickButton::Start,
-                CtrlBtn::Back     => BrickButton::Back,
-                CtrlBtn::Guide    => BrickButton::Guide,
-                CtrlBtn::LeftShoulder    => BrickButton::LeftShoulder,
-                CtrlBtn::RightShoulder    => BrickButton::RightShoulder,
-                CtrlBtn::RightStick    => BrickButton::RightStick,
-                CtrlBtn::LeftStick    => BrickButton::LeftStick,
-                _                 => return None,
-            };
-            Some(BrickInput::ButtonDown(b))
+            bindings.button_for_controller_button(*button).map(BrickInput::ButtonDown)
         }
         Event::ControllerButtonUp { button, .. } => {
-            let b = match button {
-                CtrlBtn::B        => BrickButton::A,
-                // …but you know it’s really the A button on the Brick.
-                CtrlBtn::A        => BrickButton::B,
-                CtrlBtn::Y        => BrickButton::X,
-                CtrlBtn::X        => BrickButton::Y,
-                CtrlBtn::DPadUp   => BrickButton::DPadUp,
-                CtrlBtn::DPadDown => BrickButton::DPadDown,
-                CtrlBtn::DPadLeft => BrickButton::DPadLeft,
-                CtrlBtn::DPadRight=> BrickButton::DPadRight,
-                CtrlBtn::Start    => BrickButton::Start,
-                CtrlBtn::Back     => BrickButton::Back,
-                CtrlBtn::Guide    => BrickButton::Guide,
-                CtrlBtn::LeftShoulder    => BrickButton::LeftShoulder,
-                CtrlBtn::RightShoulder    => BrickButton::RightShoulder,
-                CtrlBtn::RightStick    => BrickButton::RightStick,
-                CtrlBtn::LeftStick    => BrickButton::LeftStick,
-                _                 => return None,
-            };
-            Some(BrickInput::ButtonUp(b))
-        }
-
-        // 2) The Power key comes through as a regular KeyDown/Up
-        Event::KeyDown { keycode: Some(Keycode::Power), .. } => {
-            Some(BrickInput::ButtonDown(BrickButton::Power))
-        }
-        Event::KeyUp   { keycode: Some(Keycode::Power), .. } => {
-            Some(BrickInput::ButtonUp(  BrickButton::Power))
-        }
-
-        // 3) The “volume” buttons arrive as joystick buttons
-        Event::JoyButtonDown { button_idx: 14, .. } => {
-            Some(BrickInput::ButtonDown(BrickButton::VolumeUp))
-        }
-        Event::JoyButtonUp   { button_idx: 14, .. } => {
-            Some(BrickInput::ButtonUp(  BrickButton::VolumeUp))
-        }
-        Event::JoyButtonDown { button_idx: 13, .. } => {
-            Some(BrickInput::ButtonDown(BrickButton::VolumeDown))
-        }
-        Event::JoyButtonUp   { button_idx: 13, .. } => {
-            Some(BrickInput::ButtonUp(  BrickButton::VolumeDown))
-        }
-
-        // 4) Triggers as analog axes (SDL reports both ControllerAxisMotion and JoyAxisMotion)
+            bindings.button_for_controller_button(*button).map(BrickInput::ButtonUp)
+        }
+
+        Event::KeyDown { keycode: Some(keycode), .. } => {
+            bindings.button_for_keycode(*keycode).map(BrickInput::ButtonDown)
+        }
+        Event::KeyUp { keycode: Some(keycode), .. } => {
+            bindings.button_for_keycode(*keycode).map(BrickInput::ButtonUp)
+        }
+
+        Event::JoyButtonDown { button_idx, .. } => {
+            bindings.button_for_joy_idx(*button_idx).map(BrickInput::ButtonDown)
+        }
+        Event::JoyButtonUp { button_idx, .. } => {
+            bindings.button_for_joy_idx(*button_idx).map(BrickInput::ButtonUp)
+        }
+
         Event::ControllerAxisMotion { axis, value, .. } => {
-            let axis = match axis {
-                sdl2::controller::Axis::TriggerLeft  => BrickAxis::TriggerLeft,
-                sdl2::controller::Axis::TriggerRight => BrickAxis::TriggerRight,
-                _                                    => return None,
-            };
             // Normalize [-32768..32767] → [-1.0..1.0]
             let v = *value as f32 / 32767.0;
-            Some(BrickInput::AxisMotion { axis, value: v })
+            if let Some(brick_axis) = bindings.axis_for_controller_axis(*axis) {
+                Some(BrickInput::AxisMotion { axis: brick_axis, value: v })
+            } else {
+                stick_axis_to_dpad_input(&mut bindings.analog_dpad, *axis, v)
+            }
         }
-        Event::JoyAxisMotion { axis_idx: 2, value, .. } => {
-            let v = *value as f32 / 32767.0;
-            Some(BrickInput::AxisMotion { axis: BrickAxis::TriggerLeft,  value: v })
-        }
-        Event::JoyAxisMotion { axis_idx: 5, value, .. } => {
-            let v = *value as f32 / 32767.0;
-            Some(BrickInput::AxisMotion { axis: BrickAxis::TriggerRight, value: v })
+        Event::JoyAxisMotion { axis_idx, value, .. } => {
+            bindings.axis_for_joy_idx(*axis_idx).map(|axis| {
+                let v = *value as f32 / 32767.0;
+                BrickInput::AxisMotion { axis, value: v }
+            })
         }
 
         _ => None,
     }
-
 }
 
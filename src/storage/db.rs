@@ -1,59 +1,277 @@
 // src/storage/db.rs
-// Manages the SQLite database for storing card states.
+// Manages the SQLite database for storing card states and the review-log history.
 
+use chrono::{DateTime, Utc};
 use rusqlite::{Connection, Result};
+use rusqlite_migration::{Migrations, M};
+use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
-use crate::deck::Card;
+use crate::deck::{Card, MediaRef, Note};
+use crate::scheduler::Rating;
+
+/// The persisted scheduling fields for a single card, as read back out of `card_state`.
+/// `stability`/`difficulty` are `None` for cards that have only ever been scheduled
+/// under SM-2.
+pub struct CardState {
+    pub due: i64,
+    pub interval: u32,
+    pub ease_factor: u32,
+    pub lapses: u32,
+    pub stability: Option<f64>,
+    pub difficulty: Option<f64>,
+    pub note_id: i64,
+    pub note_hash: Option<i64>,
+    pub hidden: bool,
+}
+
+fn migrations() -> &'static Migrations<'static> {
+    static MIGRATIONS: OnceLock<Migrations<'static>> = OnceLock::new();
+    MIGRATIONS.get_or_init(|| {
+        Migrations::new(vec![
+            M::up(include_str!("sql/1-init.sql")),
+            M::up(include_str!("sql/2-sync.sql")),
+            M::up(include_str!("sql/3-review-timestamp.sql")),
+            M::up(include_str!("sql/4-notes-cache.sql")),
+        ])
+    })
+}
 
 pub struct DatabaseManager {
     conn: Connection,
 }
 
 impl DatabaseManager {
-    /// Creates a new DatabaseManager and opens a connection to the database file.
+    /// Creates a new DatabaseManager and opens a connection to the database file,
+    /// running any pending schema migrations.
     pub fn new(deck_id: &str) -> Result<Self> {
         let path = Path::new("anki/history");
         fs::create_dir_all(path).map_err(|e| rusqlite::Error::ToSqlConversionFailure(e.into()))?;
         let db_path = path.join(format!("{}.db", deck_id));
-        
-        let conn = Connection::open(db_path)?;
-        let manager = DatabaseManager { conn };
-        manager.init_schema()?;
-        
-        Ok(manager)
+
+        let mut conn = Connection::open(db_path)?;
+        migrations()
+            .to_latest(&mut conn)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(e.into()))?;
+
+        Ok(DatabaseManager { conn })
     }
 
-    /// Creates the necessary tables if they don't already exist.
-    fn init_schema(&self) -> Result<()> {
+    /// Updates the state of a single card in the database. Inserts the row on
+    /// first review; on later reviews only the scheduling columns are touched,
+    /// so sync bookkeeping (`note_id`/`note_hash`/`hidden`) is left alone.
+    /// Stamps `reviewed_at` with the current time; `recover` uses this to
+    /// tell whether a transaction-log entry still needs replaying.
+    pub fn update_card_state(&self, card: &Card) -> Result<()> {
+        self.write_card_state(card, Utc::now())
+    }
+
+    /// Replays a transaction-log entry's scheduling state into the database,
+    /// stamping `reviewed_at` with the entry's own timestamp rather than now
+    /// so a later `recover` run can still tell which entries were applied.
+    pub fn restore_card_state(&self, card: &Card, reviewed_at: DateTime<Utc>) -> Result<()> {
+        self.write_card_state(card, reviewed_at)
+    }
+
+    fn write_card_state(&self, card: &Card, reviewed_at: DateTime<Utc>) -> Result<()> {
         self.conn.execute(
-            "CREATE TABLE IF NOT EXISTS card_state (
-                id              INTEGER PRIMARY KEY,
-                due             INTEGER NOT NULL,
-                interval        INTEGER NOT NULL,
-                ease_factor     INTEGER NOT NULL,
-                lapses          INTEGER NOT NULL
-            )",
-            [],
+            "INSERT INTO card_state (id, due, interval, ease_factor, lapses, stability, difficulty, note_id, reviewed_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+             ON CONFLICT(id) DO UPDATE SET
+                due = excluded.due,
+                interval = excluded.interval,
+                ease_factor = excluded.ease_factor,
+                lapses = excluded.lapses,
+                stability = excluded.stability,
+                difficulty = excluded.difficulty,
+                reviewed_at = excluded.reviewed_at",
+            (
+                card.id,
+                card.due,
+                card.interval,
+                card.ease_factor,
+                card.lapses,
+                card.stability,
+                card.difficulty,
+                card.note_id,
+                reviewed_at.to_rfc3339(),
+            ),
         )?;
         Ok(())
     }
 
-    /// Updates the state of a single card in the database.
-    /// Uses `INSERT OR REPLACE` to handle both new and existing cards.
-    pub fn update_card_state(&self, card: &Card) -> Result<()> {
+    /// The last time `update_card_state`/`restore_card_state` wrote this
+    /// card's row, or `None` if it's never been reviewed.
+    pub fn last_reviewed_at(&self, card_id: i64) -> Result<Option<DateTime<Utc>>> {
+        let raw: Option<String> = match self.conn.query_row(
+            "SELECT reviewed_at FROM card_state WHERE id = ?1",
+            [card_id],
+            |row| row.get(0),
+        ) {
+            Ok(ts) => ts,
+            Err(rusqlite::Error::QueryReturnedNoRows) => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        Ok(raw.and_then(|ts| DateTime::parse_from_rfc3339(&ts).ok()).map(|ts| ts.with_timezone(&Utc)))
+    }
+
+    /// Loads every persisted card's scheduling state, keyed by card id, so a
+    /// scheduler can resume a deck exactly where the last session left off.
+    pub fn load_card_states(&self) -> Result<HashMap<i64, CardState>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, due, interval, ease_factor, lapses, stability, difficulty, note_id, note_hash, hidden FROM card_state",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                CardState {
+                    due: row.get(1)?,
+                    interval: row.get(2)?,
+                    ease_factor: row.get(3)?,
+                    lapses: row.get(4)?,
+                    stability: row.get(5)?,
+                    difficulty: row.get(6)?,
+                    note_id: row.get(7)?,
+                    note_hash: row.get(8)?,
+                    hidden: row.get::<_, i64>(9)? != 0,
+                },
+            ))
+        })?;
+
+        let mut states = HashMap::new();
+        for row in rows {
+            let (id, state) = row?;
+            states.insert(id, state);
+        }
+        Ok(states)
+    }
+
+    /// Records a card discovered in a fresh deck parse, storing enough (note
+    /// id and a hash of its fields) to re-match it on a later sync even if
+    /// Anki reassigns the card's id. Existing scheduling state is untouched.
+    pub fn upsert_synced_card(&self, card: &Card, note_hash: i64) -> Result<()> {
         self.conn.execute(
-            "INSERT OR REPLACE INTO card_state (id, due, interval, ease_factor, lapses)
-             VALUES (?1, ?2, ?3, ?4, ?5)",
+            "INSERT INTO card_state (id, due, interval, ease_factor, lapses, stability, difficulty, note_id, note_hash, hidden)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, 0)
+             ON CONFLICT(id) DO UPDATE SET note_id = excluded.note_id, note_hash = excluded.note_hash, hidden = 0",
             (
                 card.id,
                 card.due,
                 card.interval,
                 card.ease_factor,
                 card.lapses,
+                card.stability,
+                card.difficulty,
+                card.note_id,
+                note_hash,
+            ),
+        )?;
+        Ok(())
+    }
+
+    /// Marks a card's note as no longer present in the source deck, without
+    /// touching its scheduling state or review history.
+    pub fn mark_card_hidden(&self, card_id: i64, hidden: bool) -> Result<()> {
+        self.conn.execute(
+            "UPDATE card_state SET hidden = ?2 WHERE id = ?1",
+            (card_id, hidden as i64),
+        )?;
+        Ok(())
+    }
+
+    /// The unix timestamp (seconds) the deck file was last synced at, if any.
+    pub fn last_synced_at(&self) -> Result<Option<i64>> {
+        match self
+            .conn
+            .query_row("SELECT last_synced_at FROM sync_meta WHERE id = 1", [], |row| row.get(0))
+        {
+            Ok(ts) => Ok(Some(ts)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Records that the deck file has been synced as of `timestamp`.
+    pub fn set_last_synced_at(&self, timestamp: i64) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO sync_meta (id, last_synced_at) VALUES (1, ?1)
+             ON CONFLICT(id) DO UPDATE SET last_synced_at = excluded.last_synced_at",
+            (timestamp,),
+        )?;
+        Ok(())
+    }
+
+    /// Caches a freshly-parsed note's fields/media so a later `load_apkg`
+    /// can rebuild the `Deck` from `load_cached_notes` instead of
+    /// re-reading the .apkg, as long as the deck file hasn't changed since.
+    pub fn cache_note(&self, note: &Note) -> Result<()> {
+        let fields = note.fields.join("\x1f");
+        let media = note.media.iter().map(serialize_media_ref).collect::<Vec<_>>().join("\x1e");
+        self.conn.execute(
+            "INSERT INTO notes_cache (note_id, fields, media) VALUES (?1, ?2, ?3)
+             ON CONFLICT(note_id) DO UPDATE SET fields = excluded.fields, media = excluded.media",
+            (note.id, fields, media),
+        )?;
+        Ok(())
+    }
+
+    /// Loads every note `cache_note` has recorded, keyed by id.
+    pub fn load_cached_notes(&self) -> Result<HashMap<i64, Note>> {
+        let mut stmt = self.conn.prepare("SELECT note_id, fields, media FROM notes_cache")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?))
+        })?;
+
+        let mut notes = HashMap::new();
+        for row in rows {
+            let (note_id, fields_str, media_str) = row?;
+            let fields = fields_str.split('\x1f').map(String::from).collect();
+            let media = if media_str.is_empty() {
+                Vec::new()
+            } else {
+                media_str.split('\x1e').filter_map(deserialize_media_ref).collect()
+            };
+            notes.insert(note_id, Note { id: note_id, fields, media });
+        }
+        Ok(notes)
+    }
+
+    /// Appends a row to the review log, recording the interval transition a rating caused.
+    pub fn log_review(&self, card_id: i64, rating: Rating, previous_interval: u32, new_interval: u32) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO review_log (card_id, rating, reviewed_at, previous_interval, new_interval)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            (
+                card_id,
+                format!("{:?}", rating),
+                chrono::Utc::now().to_rfc3339(),
+                previous_interval,
+                new_interval,
             ),
         )?;
         Ok(())
     }
 }
+
+/// The inverse of `deserialize_media_ref`, used by `cache_note`.
+fn serialize_media_ref(media: &MediaRef) -> String {
+    match media {
+        MediaRef::Image(path) => format!("image:{}", path.display()),
+        MediaRef::Audio(path) => format!("audio:{}", path.display()),
+    }
+}
+
+/// Parses one `serialize_media_ref`-formatted entry back into a `MediaRef`.
+/// An entry with an unrecognized kind tag (e.g. from a future format) is
+/// dropped rather than failing the whole `load_cached_notes` read.
+fn deserialize_media_ref(entry: &str) -> Option<MediaRef> {
+    let (kind, path) = entry.split_once(':')?;
+    let path = PathBuf::from(path);
+    match kind {
+        "image" => Some(MediaRef::Image(path)),
+        "audio" => Some(MediaRef::Audio(path)),
+        _ => None,
+    }
+}
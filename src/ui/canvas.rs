@@ -37,10 +37,10 @@ impl<'a> CanvasManager<'a> {
     }
 
     /// Prepares for a new frame by setting the render target to our logical canvas
-    /// and clearing it with a background color.
-    pub fn start_frame(&mut self) -> Result<(), String> {
+    /// and clearing it with the active theme's background color.
+    pub fn start_frame(&mut self, background: Color) -> Result<(), String> {
         self.sdl_canvas.with_texture_canvas(&mut self.logical_canvas, |texture_canvas| {
-            texture_canvas.set_draw_color(Color::RGB(40, 40, 45));
+            texture_canvas.set_draw_color(background);
             texture_canvas.clear();
         }).map_err(|e| e.to_string()) // Map the SDL error to a String error
     }
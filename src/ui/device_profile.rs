@@ -0,0 +1,133 @@
+// src/ui/device_profile.rs
+// Per-device font/layout tiers, so CardBrick can target multiple handheld
+// panels: a tiny low-DPI screen wants a compact pixel font with fewer tiers,
+// a larger panel wants a proportional face with more tiers and wider margins.
+
+use std::path::{Path, PathBuf};
+
+/// A single loadable font face and its point size.
+#[derive(Clone)]
+pub struct FontTier {
+    pub path: PathBuf,
+    pub size: u32,
+}
+
+/// The named font tiers and layout margins for one screen size. `bold`,
+/// `mono`, `big`, and `sub` are `None` on profiles too small to spare a
+/// legible face (or the screen room) for that tier.
+#[derive(Clone)]
+pub struct DeviceProfile {
+    pub name: String,
+    pub logical_width: u32,
+    pub logical_height: u32,
+    /// `content_width` in `load_card_layouts`: horizontal room for a card's
+    /// front/back text.
+    pub content_margin: u32,
+    /// `max_width` in `DeckSelectionState::new`: horizontal room for a deck
+    /// title before it wraps.
+    pub selection_margin: u32,
+    pub normal: FontTier,
+    pub bold: Option<FontTier>,
+    pub mono: Option<FontTier>,
+    pub big: Option<FontTier>,
+    pub sub: Option<FontTier>,
+}
+
+impl DeviceProfile {
+    /// The profile CardBrick has always shipped with: one proportional face
+    /// (`normal`/`sub`) plus the monospace command font (`mono`) used for
+    /// in-study hints.
+    pub fn standard(assets_dir: &Path) -> Self {
+        let normal_path = assets_dir.join("font/M1MnRegular-M2Gn.ttf");
+        Self {
+            name: "standard".to_string(),
+            logical_width: 512,
+            logical_height: 384,
+            content_margin: 60,
+            selection_margin: 80,
+            normal: FontTier { path: normal_path.clone(), size: 32 },
+            bold: None,
+            mono: Some(FontTier { path: assets_dir.join("font/Ac437_Tandy1K-II_200L.ttf"), size: 10 }),
+            big: None,
+            sub: Some(FontTier { path: normal_path, size: 24 }),
+        }
+    }
+
+    /// A compact profile for a small, low-DPI panel: a single pixel font
+    /// shared across every tier it can fill, with no `bold`/`big` tier since
+    /// there's no screen room to distinguish them.
+    pub fn compact(assets_dir: &Path) -> Self {
+        let pixel_path = assets_dir.join("font/Ac437_Tandy1K-II_200L.ttf");
+        Self {
+            name: "compact".to_string(),
+            logical_width: 320,
+            logical_height: 240,
+            content_margin: 30,
+            selection_margin: 40,
+            normal: FontTier { path: pixel_path.clone(), size: 16 },
+            bold: None,
+            mono: Some(FontTier { path: pixel_path, size: 8 }),
+            big: None,
+            sub: None,
+        }
+    }
+
+    fn built_ins(assets_dir: &Path) -> Vec<Self> {
+        vec![Self::standard(assets_dir), Self::compact(assets_dir)]
+    }
+
+    /// Picks the widest built-in profile (plus whatever `[[profiles]]`
+    /// entries `profiles_path` adds) that still fits `screen_width`, so a
+    /// bigger panel gets the roomier tier set and a tiny one falls back to
+    /// `compact`. Falls back to `standard` if nothing fits; a missing or
+    /// unparseable override file just leaves the built-ins to pick from.
+    pub fn load(assets_dir: &Path, profiles_path: &Path, screen_width: u32) -> Self {
+        let mut profiles = Self::built_ins(assets_dir);
+        if let Ok(contents) = std::fs::read_to_string(profiles_path) {
+            match parse_profiles(&contents, assets_dir) {
+                Ok(mut overrides) => profiles.append(&mut overrides),
+                Err(e) => eprintln!("ignoring invalid device profiles at {:?}: {}", profiles_path, e),
+            }
+        }
+
+        profiles.into_iter()
+            .filter(|p| p.logical_width <= screen_width)
+            .max_by_key(|p| p.logical_width)
+            .unwrap_or_else(|| Self::standard(assets_dir))
+    }
+}
+
+fn parse_profiles(contents: &str, assets_dir: &Path) -> Result<Vec<DeviceProfile>, String> {
+    let value: toml::Value = contents.parse().map_err(|e: toml::de::Error| e.to_string())?;
+    let entries = value.get("profiles").and_then(|v| v.as_array()).cloned().unwrap_or_default();
+
+    let mut profiles = Vec::new();
+    for entry in entries {
+        let table = entry.as_table().ok_or("each [[profiles]] entry must be a table")?;
+        let name = table.get("name").and_then(|v| v.as_str()).unwrap_or("custom").to_string();
+        let normal = table.get("normal")
+            .and_then(|v| parse_font_tier(v, assets_dir))
+            .ok_or_else(|| format!("profile '{}' is missing a `normal` font tier", name))?;
+
+        profiles.push(DeviceProfile {
+            logical_width: table.get("logical_width").and_then(|v| v.as_integer()).unwrap_or(512) as u32,
+            logical_height: table.get("logical_height").and_then(|v| v.as_integer()).unwrap_or(384) as u32,
+            content_margin: table.get("content_margin").and_then(|v| v.as_integer()).unwrap_or(60) as u32,
+            selection_margin: table.get("selection_margin").and_then(|v| v.as_integer()).unwrap_or(80) as u32,
+            bold: table.get("bold").and_then(|v| parse_font_tier(v, assets_dir)),
+            mono: table.get("mono").and_then(|v| parse_font_tier(v, assets_dir)),
+            big: table.get("big").and_then(|v| parse_font_tier(v, assets_dir)),
+            sub: table.get("sub").and_then(|v| parse_font_tier(v, assets_dir)),
+            name,
+            normal,
+        });
+    }
+    Ok(profiles)
+}
+
+fn parse_font_tier(value: &toml::Value, assets_dir: &Path) -> Option<FontTier> {
+    let table = value.as_table()?;
+    let path = table.get("path").and_then(|v| v.as_str())?;
+    let size = table.get("size").and_then(|v| v.as_integer())? as u32;
+    Some(FontTier { path: assets_dir.join(path), size })
+}
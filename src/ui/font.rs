@@ -5,85 +5,384 @@
 use sdl2::surface::Surface;
 
 use crate::Config;
+use lru::LruCache;
 use sdl2::pixels::Color;
 use std::collections::VecDeque;
+use std::num::NonZeroUsize;
 use sdl2::rect::Rect;
-use sdl2::render::Canvas;
+use sdl2::render::{Canvas, Texture, TextureCreator};
 use sdl2::ttf::{Font, Sdl2TtfContext, FontStyle};
-use sdl2::video::Window;
+use sdl2::video::{Window, WindowContext};
+use unicode_segmentation::UnicodeSegmentation;
 use crate::debug::Tracer;
 use crate::deck::html_parser::TextSpan;
 
+/// Characters that may never start a line (kinsoku shori): closing brackets,
+/// and the small kana/punctuation that attach to the character before them.
+const FORBIDDEN_LINE_START: &[char] = &[
+    '、', '。', '」', '』', '）', '！', '？', 'ぁ', 'ぃ', 'ぅ', 'ぇ', 'ぉ', 'ゃ', 'ゅ', 'ょ', 'っ', 'ー',
+];
+/// Characters that may never end a line: opening brackets, which must stay
+/// attached to whatever follows them.
+const FORBIDDEN_LINE_END: &[char] = &['「', '『', '（'];
+
+fn starts_with_forbidden_line_start(unit: &str) -> bool {
+    unit.chars().next().map_or(false, |c| FORBIDDEN_LINE_START.contains(&c))
+}
+
+fn ends_with_forbidden_line_end(unit: &str) -> bool {
+    unit.chars().last().map_or(false, |c| FORBIDDEN_LINE_END.contains(&c))
+}
+
+fn is_cjk(c: char) -> bool {
+    matches!(c as u32,
+        0x3040..=0x30FF   // Hiragana + Katakana
+        | 0x4E00..=0x9FFF // CJK Unified Ideographs
+        | 0x3400..=0x4DBF // CJK Extension A
+        | 0xFF00..=0xFFEF // Fullwidth forms
+    )
+}
+
+/// Furigana readings render at roughly half the base font's point size.
+fn ruby_font_size_for(font_size: u16) -> u16 {
+    ((font_size as f32) * 0.5).round().max(1.0) as u16
+}
+
+/// Height reserved for one output line: the base font's line height, plus a
+/// furigana strip above it if annotating and any span on the line carries a
+/// reading.
+fn line_height_for(line_spans: &[TextSpan], annotate_ruby: bool, base_height: i32, ruby_height: i32) -> i32 {
+    let has_ruby = annotate_ruby && line_spans.iter().any(|s| s.is_ruby_base && s.ruby_text.is_some());
+    if has_ruby { base_height + ruby_height } else { base_height }
+}
+
+/// Segments `text` into break opportunities: UAX#29 word boundaries for
+/// Latin text (so whole words stay together), but one break unit per
+/// character for any word-bounds unit containing CJK, since each ideograph
+/// is its own break opportunity under UAX#14.
+fn break_units(text: &str) -> Vec<&str> {
+    let mut units = Vec::new();
+    for word in text.split_word_bounds() {
+        if word.chars().any(is_cjk) {
+            units.extend(word.char_indices().map(|(i, c)| &word[i..i + c.len_utf8()]));
+        } else {
+            units.push(word);
+        }
+    }
+    units
+}
+
+/// Horizontal alignment for a `TextLayout`, applied per line at draw time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Alignment {
+    #[default]
+    Left,
+    Center,
+    Right,
+    Justify,
+}
+
 /// Holds a pre-calculated text layout for efficient rendering and scrolling.
 pub struct TextLayout {
     // Each inner Vec<TextSpan> represents a single line of text with its styled segments.
     pub lines: Vec<Vec<TextSpan>>,
     pub total_height: i32,
     pub scroll_offset: i32,
+    /// The width the lines were wrapped to; alignment offsets are computed against this.
+    pub max_width: u32,
+    pub alignment: Alignment,
+    // Parallel to `lines`. True when a line ends a paragraph (explicit
+    // newline or end of text) rather than being cut off by wrapping.
+    line_ends_paragraph: Vec<bool>,
+    // Parallel to `lines`. The pixel height of each line, including a
+    // furigana strip above it when that line annotates a ruby-base span.
+    line_heights: Vec<i32>,
+}
+
+/// The result of `FontManager::hit_test`: where a screen point landed within
+/// a `TextLayout`.
+#[derive(Debug, Clone, Copy)]
+pub struct HitTestResult {
+    pub line_index: usize,
+    pub span_index: usize,
+    pub byte_offset: usize,
+    pub character: char,
+}
+
+/// How many rendered glyph textures `FontManager` keeps around before
+/// evicting the least-recently-used one.
+const GLYPH_CACHE_CAPACITY: usize = 512;
+
+/// Identifies a single rendered glyph. Two spans that render the same
+/// character with the same style/color/size hit the same cache entry.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct GlyphKey {
+    ch: char,
+    is_bold: bool,
+    is_italic: bool,
+    color: (u8, u8, u8, u8),
+    font_size: u16,
 }
 
-pub struct FontManager<'a, 'b> {
+pub struct FontManager<'a, 'b, 'c> {
     #[allow(dead_code)] // ttf_context must be kept alive, but is not read directly.
     ttf_context: &'a Sdl2TtfContext,
     font: Font<'a, 'b>,
+    font_size: u16,
+    texture_creator: &'c TextureCreator<WindowContext>,
+    // Glyph-level texture cache, keyed by `GlyphKey`. Rendering a whole string
+    // every frame was the single biggest per-frame cost; caching at the
+    // glyph/grapheme level and blitting cached quads at advancing x-offsets
+    // turns steady-state redraw into cache hits.
+    glyph_cache: LruCache<GlyphKey, Texture<'c>>,
+    // Loaded once at `ruby_font_size_for(font_size)` and used only to draw
+    // the small furigana reading above a ruby-base span. Shares
+    // `glyph_cache` with `font` — their different `font_size` in `GlyphKey`
+    // keeps the two fonts' glyphs from colliding.
+    ruby_font: Font<'a, 'b>,
+    ruby_font_size: u16,
+    // Additional fonts consulted, in order, for any character the primary
+    // font can't render — e.g. a CJK or emoji font backing a Latin body
+    // font. Index 0 in `split_runs_by_font`'s resolved font index always
+    // means `font` itself; index `i + 1` means `fallback_fonts[i]`.
+    fallback_fonts: Vec<Font<'a, 'b>>,
+    // Extra pixels inserted after each glyph's advance (never after the last
+    // glyph of a measured/drawn segment). Negative values track text in.
+    letter_spacing: i32,
+    // Whether sdl2_ttf kerning is enabled on every loaded font.
+    kerning: bool,
+    // Color new glyphs are rendered in. Part of `GlyphKey`, so changing it
+    // doesn't invalidate previously-cached glyphs in another color.
+    text_color: Color,
 }
 
 impl TextSpan {
-    pub fn text_to_use(&self, use_ruby: bool) -> &str {
-        if use_ruby {
-            // Use the ruby text if available, otherwise fall back to the base text.
-            self.ruby_text.as_deref().unwrap_or(&self.text)
-        } else {
-            &self.text
-        }
+    /// The text to measure/draw for this span. Furigana is layered on top of
+    /// the base text as an annotation (see `FontManager::layout_text_binary`
+    /// and `draw_layout`), never substituted for it, so this always returns
+    /// the base text.
+    pub fn text_to_use(&self) -> &str {
+        &self.text
     }
 }
 
-impl<'a, 'b> FontManager<'a, 'b> {
-    pub fn new(ttf_context: &'a Sdl2TtfContext, font_path: &str, font_size: u16) -> Result<Self, String> {
+impl<'a, 'b, 'c> FontManager<'a, 'b, 'c> {
+    pub fn new(
+        ttf_context: &'a Sdl2TtfContext,
+        font_path: &str,
+        font_size: u16,
+        texture_creator: &'c TextureCreator<WindowContext>,
+    ) -> Result<Self, String> {
         let font = ttf_context.load_font(font_path, font_size)?;
-        Ok(FontManager { ttf_context, font })
+        let ruby_font_size = ruby_font_size_for(font_size);
+        let ruby_font = ttf_context.load_font(font_path, ruby_font_size)?;
+        Ok(FontManager {
+            ttf_context,
+            font,
+            font_size,
+            texture_creator,
+            glyph_cache: LruCache::new(NonZeroUsize::new(GLYPH_CACHE_CAPACITY).unwrap()),
+            ruby_font,
+            ruby_font_size,
+            fallback_fonts: Vec::new(),
+            letter_spacing: 0,
+            kerning: true,
+            text_color: Color::RGBA(255, 255, 255, 255),
+        })
+    }
+
+    /// Sets the color new glyphs render in (e.g. the active `Theme`'s
+    /// `text`/`hint_text`). Takes effect on the next `draw_layout`/
+    /// `draw_single_line` call; doesn't require `clear_cache` since color is
+    /// already part of `GlyphKey`.
+    pub fn set_text_color(&mut self, color: Color) {
+        self.text_color = color;
+    }
+
+    /// Sets the extra spacing (in pixels) inserted between glyphs; affects
+    /// both measurement and drawing so wrapping stays consistent with what's
+    /// rendered. Does not require `clear_cache` — cached glyph textures are
+    /// unaffected, only the cursor advance between them changes.
+    pub fn set_letter_spacing(&mut self, letter_spacing: i32) {
+        self.letter_spacing = letter_spacing;
+    }
+
+    /// Enables or disables sdl2_ttf kerning on every loaded font (primary,
+    /// ruby, and fallback chain). Changes how multi-character runs measure,
+    /// so cached glyph textures are dropped to avoid mixing pre/post-toggle
+    /// advances within a frame.
+    pub fn set_kerning(&mut self, enabled: bool) {
+        self.kerning = enabled;
+        self.font.set_kerning(enabled);
+        self.ruby_font.set_kerning(enabled);
+        for fallback in &mut self.fallback_fonts {
+            fallback.set_kerning(enabled);
+        }
+        self.clear_cache();
     }
 
+    /// Like `new`, but additionally loads `fallback_path` (if given) as a
+    /// fallback font: any character the primary font can't render is looked
+    /// up there instead, so a single span can mix scripts (e.g. Latin text
+    /// with emoji) without the caller pre-splitting it.
+    pub fn new_with_fallback(
+        ttf_context: &'a Sdl2TtfContext,
+        font_path: &str,
+        fallback_path: Option<&str>,
+        font_size: u16,
+        texture_creator: &'c TextureCreator<WindowContext>,
+    ) -> Result<Self, String> {
+        let mut manager = Self::new(ttf_context, font_path, font_size, texture_creator)?;
+        if let Some(path) = fallback_path {
+            manager.fallback_fonts.push(ttf_context.load_font(path, font_size)?);
+        }
+        Ok(manager)
+    }
 
-    /// Get the pixel dimensions of a string of text.
-    /// This considers the current style the font is set to.
+    /// Resolves which font should render `ch`: the primary font if it has
+    /// the glyph, otherwise the first fallback font that does, otherwise the
+    /// primary font anyway (renders as tofu, but keeps layout moving). The
+    /// index is stable for `font_mut`: 0 is the primary font, `i + 1` is
+    /// `fallback_fonts[i]`.
+    fn font_index_for_char(&self, ch: char) -> usize {
+        if self.font.find_glyph(ch).is_some() {
+            return 0;
+        }
+        for (i, fallback) in self.fallback_fonts.iter().enumerate() {
+            if fallback.find_glyph(ch).is_some() {
+                return i + 1;
+            }
+        }
+        0
+    }
+
+    fn font_mut(&mut self, font_index: usize) -> &mut Font<'a, 'b> {
+        if font_index == 0 {
+            &mut self.font
+        } else {
+            &mut self.fallback_fonts[font_index - 1]
+        }
+    }
+
+    /// Splits `text` into runs of consecutive characters resolved to the
+    /// same font in the fallback chain, so mixed-script text can be
+    /// measured/drawn per physical font while staying one logical `TextSpan`.
+    fn split_runs_by_font(&self, text: &str) -> Vec<(usize, String)> {
+        let mut runs: Vec<(usize, String)> = Vec::new();
+        for ch in text.chars() {
+            let idx = self.font_index_for_char(ch);
+            if let Some(last) = runs.last_mut() {
+                if last.0 == idx {
+                    last.1.push(ch);
+                    continue;
+                }
+            }
+            runs.push((idx, ch.to_string()));
+        }
+        runs
+    }
+
+    /// Drops every cached glyph texture. Call this after changing font size
+    /// (e.g. a new `Font` is loaded at a different point size), since cached
+    /// textures from the old size would otherwise linger under stale keys.
+    pub fn clear_cache(&mut self) {
+        self.glyph_cache.clear();
+    }
+
+
+    /// Get the pixel dimensions of a string of text. Splits across the
+    /// fallback font chain per `split_runs_by_font` and sums each run's
+    /// advance, so mixed-script text measures correctly without the caller
+    /// pre-splitting it.
     pub fn size_of_text_with_style(&mut self, text: &str, is_bold: bool, is_italic: bool) -> Result<(u32, u32), String> {
-        let original_style = self.font.get_style();
-        let mut current_style = original_style;
-        if is_bold { current_style = current_style | FontStyle::BOLD; }
-        if is_italic { current_style = current_style | FontStyle::ITALIC; }
-        self.font.set_style(current_style);
-
-        let result = self.font.size_of(text).map_err(|e| e.to_string());
-        self.font.set_style(original_style); // Reset style
-        result
+        let mut total_width = 0u32;
+        let mut max_height = 0u32;
+        for (font_idx, run_text) in self.split_runs_by_font(text) {
+            let font = self.font_mut(font_idx);
+            let original_style = font.get_style();
+            let mut current_style = original_style;
+            if is_bold { current_style = current_style | FontStyle::BOLD; }
+            if is_italic { current_style = current_style | FontStyle::ITALIC; }
+            font.set_style(current_style);
+
+            let result = font.size_of(&run_text).map_err(|e| e.to_string());
+            font.set_style(original_style); // Reset style
+            let (w, h) = result?;
+            total_width += w;
+            max_height = max_height.max(h);
+        }
+
+        let total_chars = text.chars().count();
+        if total_chars > 1 {
+            let spacing_total = self.letter_spacing as i64 * (total_chars as i64 - 1);
+            total_width = (total_width as i64 + spacing_total).max(0) as u32;
+        }
+        Ok((total_width, max_height))
     }
 
-    /// Finds the character index to split a TextSpan so it fits within the available width.
-    /// This is the efficient binary search method.
-    fn find_split_index(&mut self, span: &TextSpan, space_left: u32, use_ruby: bool) -> Result<usize, String> {
-        let text = span.text_to_use(use_ruby);
-        let mut current_width = 0;
-        let mut last_valid_split_point = 0;
-
-        // Iterate character by character to respect UTF-8 boundaries
-        for (byte_index, char) in text.char_indices() {
-            let char_str = char.to_string();
-            let (char_width, _) = self.size_of_text_with_style(&char_str, span.is_bold, span.is_italic)?;
-            
-            if current_width + char_width > space_left {
-                // This character does not fit, so the split point is before it.
-                return Ok(last_valid_split_point);
+    /// Finds the byte offset to split a TextSpan so the text before it fits
+    /// within `space_left`. Unlike a plain per-character split, this packs
+    /// whole break units (words on Latin text, one unit per CJK ideograph)
+    /// and applies kinsoku shori: when a forbidden-line-start character would
+    /// begin the next line, the break moves one unit earlier so it carries its
+    /// predecessor down with it, and a forbidden-line-end character is pulled
+    /// down to the next line with what follows it.
+    fn find_kinsoku_split_index(&mut self, span: &TextSpan, space_left: u32) -> Result<usize, String> {
+        let text = span.text_to_use();
+        let units = break_units(text);
+        if units.is_empty() {
+            return Ok(0);
+        }
+
+        let mut current_width = 0u32;
+        let mut fitting_units = 0usize;
+        for unit in &units {
+            let (unit_width, _) = self.size_of_text_with_style(unit, span.is_bold, span.is_italic)?;
+            if current_width + unit_width > space_left {
+                break;
             }
-            
-            current_width += char_width;
-            // The split point is after the current character.
-            last_valid_split_point = byte_index + char.len_utf8();
+            current_width += unit_width;
+            fitting_units += 1;
+        }
+
+        // Not even the first unit fits, or the whole span fits: nothing for
+        // kinsoku to adjust. The caller handles the "nothing fits" case.
+        if fitting_units == 0 || fitting_units == units.len() {
+            return Ok(units[..fitting_units].iter().map(|u| u.len()).sum());
         }
 
-        // If the whole string fits, the split point is at the end.
-        Ok(last_valid_split_point)
+        let original_fitting_units = fitting_units;
+
+        // Don't let a forbidden-start character (closing bracket, small kana) begin the next line.
+        while fitting_units > 0 && starts_with_forbidden_line_start(units[fitting_units]) {
+            fitting_units -= 1;
+        }
+        // Don't leave a forbidden-end character (opening bracket) dangling alone at this line's end.
+        while fitting_units > 0 && ends_with_forbidden_line_end(units[fitting_units - 1]) {
+            fitting_units -= 1;
+        }
+
+        if fitting_units == 0 {
+            // Kinsoku pulled everything back onto the next line; fall back to
+            // the pre-kinsoku split so we still make forward progress.
+            fitting_units = original_fitting_units;
+        }
+
+        Ok(units[..fitting_units].iter().map(|u| u.len()).sum())
+    }
+
+    /// Width to reserve for `span` when packing a line. Ordinarily just the
+    /// base text's rendered width; when annotating with furigana, widened to
+    /// fit the reading if it would otherwise overhang the base.
+    fn layout_width_for_span(&mut self, span: &TextSpan, annotate_ruby: bool) -> Result<u32, String> {
+        let (base_width, _) = self.size_of_text_with_style(span.text_to_use(), span.is_bold, span.is_italic)?;
+        if annotate_ruby && span.is_ruby_base {
+            if let Some(reading) = &span.ruby_text {
+                let (reading_width, _) = self.ruby_font.size_of(reading).map_err(|e| e.to_string())?;
+                return Ok(base_width.max(reading_width));
+            }
+        }
+        Ok(base_width)
     }
 
 
@@ -113,46 +412,52 @@ impl<'a, 'b> FontManager<'a, 'b> {
 
         // --- STAGE 2: Corrected Layout Engine ---
         let mut lines: Vec<Vec<TextSpan>> = Vec::new();
+        // Parallel to `lines`: true when a line ended because of an explicit
+        // newline (or is the last line overall), false when it ended purely
+        // because of width-driven wrapping. Justify alignment only stretches
+        // wrapped lines, never the last line of a paragraph.
+        let mut line_ends_paragraph: Vec<bool> = Vec::new();
+        let mut line_heights: Vec<i32> = Vec::new();
         let mut current_line_spans: Vec<TextSpan> = Vec::new();
         let mut current_line_width = 0;
         let line_height = self.font.height();
+        let ruby_line_height = self.ruby_font.height();
 
         while let Some(span) = processed_spans.pop_front() {
             if span.is_newline {
+                line_heights.push(line_height_for(&current_line_spans, use_ruby, line_height, ruby_line_height));
                 lines.push(current_line_spans);
+                line_ends_paragraph.push(true);
                 current_line_spans = Vec::new();
                 current_line_width = 0;
                 continue;
             }
 
-            let text_for_layout = span.text_to_use(use_ruby);
             let space_left = max_width.saturating_sub(current_line_width);
-            let (span_width, _) = self.size_of_text_with_style(text_for_layout, span.is_bold, span.is_italic)?;
+            let span_width = self.layout_width_for_span(&span, use_ruby)?;
 
             if span_width <= space_left {
                 current_line_spans.push(span);
                 current_line_width += span_width;
             } else {
-                let split_byte_index = self.find_split_index(&span, space_left, use_ruby)?;
+                let split_byte_index = self.find_kinsoku_split_index(&span, space_left)?;
 
                 if split_byte_index > 0 {
                     // FIX: By calling .to_string(), we create an owned String and drop the borrow on `span`.
                     // This allows `span` to be moved into `remaining_span` later without a borrow checker error.
-                    let text_to_split = span.text_to_use(use_ruby).to_string();
+                    let text_to_split = span.text_to_use().to_string();
                     let (fits, remaining) = text_to_split.split_at(split_byte_index);
-                    
+
                     let mut fit_span = span.clone();
                     let mut remaining_span = span;
 
-                    if use_ruby {
-                        fit_span.ruby_text = Some(fits.to_string());
-                        remaining_span.ruby_text = Some(remaining.to_string());
-                        remaining_span.text = String::new();
-                    } else {
-                        fit_span.text = fits.to_string();
-                        remaining_span.text = remaining.to_string();
-                    }
-                    
+                    fit_span.text = fits.to_string();
+                    remaining_span.text = remaining.to_string();
+                    // A ruby reading annotates the whole base; if the base had
+                    // to be split across a wrap, keep the reading on the
+                    // first fragment only so it isn't drawn twice.
+                    remaining_span.ruby_text = None;
+
                     current_line_spans.push(fit_span);
                     processed_spans.push_front(remaining_span);
 
@@ -167,24 +472,18 @@ impl<'a, 'b> FontManager<'a, 'b> {
                     } else {
                         // The line is empty, but the word is still too long.
                         // Force a split by taking at least one character to prevent an infinite loop.
-                        let text_to_split = span.text_to_use(use_ruby).to_string();
+                        let text_to_split = span.text_to_use().to_string();
                         let mut char_iter = text_to_split.chars();
                         if let Some(first_char) = char_iter.next() {
                             let split_at = first_char.len_utf8();
                             let (fits, remaining) = text_to_split.split_at(split_at);
-                            
+
                             let mut fit_span = span.clone();
                             let mut remaining_span = span;
 
-                            if use_ruby {
-                                fit_span.ruby_text = Some(fits.to_string());
-                                remaining_span.ruby_text = Some(remaining.to_string());
-                                // We keep the base text with the first part and clear it for the rest.
-                                remaining_span.text = String::new(); 
-                            } else {
-                                fit_span.text = fits.to_string();
-                                remaining_span.text = remaining.to_string();
-                            }
+                            fit_span.text = fits.to_string();
+                            remaining_span.text = remaining.to_string();
+                            remaining_span.ruby_text = None;
 
                             current_line_spans.push(fit_span);
                             if !remaining.is_empty() {
@@ -199,67 +498,281 @@ impl<'a, 'b> FontManager<'a, 'b> {
                     // #################################################################
                 }
 
+                line_heights.push(line_height_for(&current_line_spans, use_ruby, line_height, ruby_line_height));
                 lines.push(current_line_spans);
+                line_ends_paragraph.push(false); // wrapped, not a paragraph break
                 current_line_spans = Vec::new();
                 current_line_width = 0;
             }
         }
 
         if !current_line_spans.is_empty() {
+            line_heights.push(line_height_for(&current_line_spans, use_ruby, line_height, ruby_line_height));
             lines.push(current_line_spans);
+            line_ends_paragraph.push(true);
         }
         if lines.is_empty() {
             lines.push(Vec::new());
+            line_ends_paragraph.push(true);
+            line_heights.push(line_height);
         }
 
-        let total_height = line_height * lines.len() as i32;
-        Ok(TextLayout { lines, total_height, scroll_offset: 0 })
+        let total_height = line_heights.iter().sum();
+        Ok(TextLayout {
+            lines,
+            total_height,
+            scroll_offset: 0,
+            max_width,
+            alignment: Alignment::default(),
+            line_ends_paragraph,
+            line_heights,
+        })
     }
 
-    /// Renders a pre-calculated TextLayout to the screen.
+    /// Renders a pre-calculated TextLayout to the screen, offsetting each
+    /// line according to `layout.alignment`. When `show_ruby` is set, a
+    /// ruby-base span with a reading gets that reading drawn at reduced size,
+    /// centered above it, in a strip reserved by `layout_text_binary` — the
+    /// base text itself is always drawn, never replaced.
     pub fn draw_layout(&mut self, canvas: &mut Canvas<Window>, layout: &TextLayout, x: i32, y: i32, show_ruby: bool) -> Result<(), String> {
-        let line_height = self.font.height() as i32;
+        let base_line_height = self.font.height() as i32;
         let mut current_y = y - layout.scroll_offset;
 
-        for line_spans in &layout.lines {
-            if current_y > -line_height && current_y < canvas.viewport().height() as i32 {
-                let mut current_x = x;
+        for (line_idx, line_spans) in layout.lines.iter().enumerate() {
+            let this_line_height = layout.line_heights.get(line_idx).copied().unwrap_or(base_line_height);
+            if current_y > -this_line_height && current_y < canvas.viewport().height() as i32 {
+                let ruby_strip = (this_line_height - base_line_height).max(0);
+                let base_y = current_y + ruby_strip;
+                let (mut current_x, extra_per_gap) = self.line_draw_origin(layout, line_idx, x, show_ruby)?;
+
                 for span in line_spans {
-                    let text_to_draw = span.text_to_use(show_ruby);
-                    let (text_w, _) = self.draw_text_span_segment(canvas, text_to_draw, current_x, current_y, span.is_bold, span.is_italic)?;
-                    current_x += text_w as i32;
+                    let text_to_draw = span.text_to_use();
+                    let (text_w, _) = self.draw_text_span_segment(canvas, text_to_draw, current_x, base_y, span.is_bold, span.is_italic)?;
+                    if show_ruby && span.is_ruby_base {
+                        if let Some(reading) = &span.ruby_text {
+                            self.draw_ruby_annotation(canvas, reading, current_x, current_y, text_w)?;
+                        }
+                    }
+                    current_x += text_w as i32 + extra_per_gap;
                 }
             }
-            current_y += line_height;
+            current_y += this_line_height;
         }
         Ok(())
     }
 
+    /// Sums the rendered width of every span on a line, used to compute
+    /// alignment offsets against `TextLayout::max_width`.
+    fn measure_line_width(&mut self, line_spans: &[TextSpan], _show_ruby: bool) -> Result<u32, String> {
+        let mut width = 0u32;
+        for span in line_spans {
+            let text = span.text_to_use();
+            let (span_width, _) = self.size_of_text_with_style(text, span.is_bold, span.is_italic)?;
+            width += span_width;
+        }
+        Ok(width)
+    }
+
+    /// Draws `reading` at `ruby_font_size`, horizontally centered over a base
+    /// span of `base_width` pixels starting at `base_x`, on the ruby strip
+    /// above the base line (`strip_y`).
+    fn draw_ruby_annotation(&mut self, canvas: &mut Canvas<Window>, reading: &str, base_x: i32, strip_y: i32, base_width: u32) -> Result<(), String> {
+        if reading.is_empty() {
+            return Ok(());
+        }
+        let (reading_width, _) = self.ruby_font.size_of(reading).map_err(|e| e.to_string())?;
+        let offset = (base_width as i32 - reading_width as i32) / 2;
+        let x = base_x + offset.max(0);
+        self.draw_ruby_text_segment(canvas, reading, x, strip_y)?;
+        Ok(())
+    }
+
+    /// Renders a furigana reading with the smaller `ruby_font`, glyph by
+    /// glyph, mirroring `draw_text_span_segment`.
+    fn draw_ruby_text_segment(&mut self, canvas: &mut Canvas<Window>, text: &str, x: i32, y: i32) -> Result<(u32, u32), String> {
+        if text.is_empty() {
+            return Ok((0, 0));
+        }
+
+        let color = self.text_color;
+        let mut cursor_x = x;
+        let mut max_height = 0u32;
+
+        for ch in text.chars() {
+            let key = GlyphKey {
+                ch,
+                is_bold: false,
+                is_italic: false,
+                color: (color.r, color.g, color.b, color.a),
+                font_size: self.ruby_font_size,
+            };
+
+            if self.glyph_cache.get(&key).is_none() {
+                let mut buf = [0u8; 4];
+                let glyph_str = ch.encode_utf8(&mut buf);
+                let surface = self.ruby_font.render(glyph_str).blended(color).map_err(|e| e.to_string())?;
+                let texture = self.texture_creator
+                    .create_texture_from_surface(&surface)
+                    .map_err(|e| e.to_string())?;
+                self.glyph_cache.put(key.clone(), texture);
+            }
+
+            let texture = self.glyph_cache.get(&key).expect("glyph was just cached");
+            let query = texture.query();
+            let target_rect = Rect::new(cursor_x, y, query.width, query.height);
+            canvas.copy(texture, None, Some(target_rect))?;
+            cursor_x += query.width as i32;
+            max_height = max_height.max(query.height);
+        }
+
+        Ok(((cursor_x - x) as u32, max_height))
+    }
+
+    /// Computes where a line's first span should start drawing and how much
+    /// extra space to insert between spans, given `layout.alignment`. Shared
+    /// by `draw_layout` and `hit_test` so tap coordinates line up with what's
+    /// actually on screen.
+    fn line_draw_origin(&mut self, layout: &TextLayout, line_idx: usize, x: i32, show_ruby: bool) -> Result<(i32, i32), String> {
+        let line_spans = match layout.lines.get(line_idx) {
+            Some(spans) => spans,
+            None => return Ok((x, 0)),
+        };
+        let line_width = self.measure_line_width(line_spans, show_ruby)? as i32;
+        let slack = (layout.max_width as i32 - line_width).max(0);
+        let ends_paragraph = layout.line_ends_paragraph.get(line_idx).copied().unwrap_or(true);
+
+        Ok(match layout.alignment {
+            Alignment::Left => (x, 0),
+            Alignment::Center => (x + slack / 2, 0),
+            Alignment::Right => (x + slack, 0),
+            Alignment::Justify if !ends_paragraph && line_spans.len() > 1 => {
+                (x, slack / (line_spans.len() as i32 - 1))
+            }
+            Alignment::Justify => (x, 0),
+        })
+    }
+
+    /// Maps a screen-space point back to the character it falls on, for
+    /// tap-to-lookup. `origin` is where the layout is drawn (as passed to
+    /// `draw_layout`). Returns `None` if the point falls outside every line,
+    /// on an empty span, or past the last glyph of the matched line.
+    pub fn hit_test(&mut self, layout: &TextLayout, x: i32, y: i32, origin: (i32, i32), show_ruby: bool) -> Option<HitTestResult> {
+        let base_line_height = self.font.height() as i32;
+        let relative_y = y - origin.1 + layout.scroll_offset;
+        if relative_y < 0 || base_line_height <= 0 {
+            return None;
+        }
+
+        let mut line_index = 0usize;
+        let mut cursor = 0i32;
+        loop {
+            if line_index >= layout.lines.len() {
+                return None;
+            }
+            let this_line_height = layout.line_heights.get(line_index).copied().unwrap_or(base_line_height);
+            if this_line_height <= 0 {
+                return None;
+            }
+            if relative_y < cursor + this_line_height {
+                break;
+            }
+            cursor += this_line_height;
+            line_index += 1;
+        }
+        let line_spans = layout.lines.get(line_index)?;
+
+        let (mut current_x, extra_per_gap) = self.line_draw_origin(layout, line_index, origin.0, show_ruby).ok()?;
+
+        for (span_index, span) in line_spans.iter().enumerate() {
+            let text = span.text_to_use();
+            if text.is_empty() {
+                continue;
+            }
+            let (span_width, _) = self.size_of_text_with_style(text, span.is_bold, span.is_italic).ok()?;
+            let span_end = current_x + span_width as i32;
+
+            if x >= current_x && x < span_end {
+                let char_offsets: Vec<usize> = text.char_indices().map(|(i, _)| i).collect();
+                let mut cumulative = Vec::with_capacity(char_offsets.len() + 1);
+                cumulative.push(current_x);
+                let mut running = current_x;
+                for ch in text.chars() {
+                    let (char_width, _) = self.size_of_text_with_style(&ch.to_string(), span.is_bold, span.is_italic).ok()?;
+                    running += char_width as i32;
+                    cumulative.push(running);
+                }
+
+                let char_index = match cumulative.binary_search(&x) {
+                    Ok(i) => i,
+                    Err(i) => i.saturating_sub(1),
+                };
+                let byte_offset = *char_offsets.get(char_index)?;
+                let character = text[byte_offset..].chars().next()?;
+                return Some(HitTestResult { line_index, span_index, byte_offset, character });
+            }
+
+            current_x = span_end + extra_per_gap;
+        }
+
+        None
+    }
+
     /// Renders a single segment of text with specified bold/italic styles.
+    /// Composes the segment from cached per-glyph textures, blitting each at
+    /// an advancing x-offset, instead of rendering the whole string fresh.
+    /// Each glyph is resolved to whichever font in the fallback chain can
+    /// render it, so a segment mixing scripts draws correctly in one pass.
     fn draw_text_span_segment(&mut self, canvas: &mut Canvas<Window>, text: &str, x: i32, y: i32, is_bold: bool, is_italic: bool) -> Result<(u32, u32), String> {
         if text.is_empty() {
             return Ok((0, 0));
         }
 
-        let original_style = self.font.get_style();
-        let mut current_style = original_style;
-        if is_bold { current_style = current_style | FontStyle::BOLD; }
-        if is_italic { current_style = current_style | FontStyle::ITALIC; }
-        self.font.set_style(current_style);
+        let color = self.text_color;
+        let mut cursor_x = x;
+        let mut max_height = 0u32;
+        let total_chars = text.chars().count();
+
+        for (i, ch) in text.chars().enumerate() {
+            let key = GlyphKey {
+                ch,
+                is_bold,
+                is_italic,
+                color: (color.r, color.g, color.b, color.a),
+                font_size: self.font_size,
+            };
+
+            if self.glyph_cache.get(&key).is_none() {
+                let font_idx = self.font_index_for_char(ch);
+                let mut buf = [0u8; 4];
+                let glyph_str = ch.encode_utf8(&mut buf);
+
+                let font = self.font_mut(font_idx);
+                let original_style = font.get_style();
+                let mut current_style = original_style;
+                if is_bold { current_style = current_style | FontStyle::BOLD; }
+                if is_italic { current_style = current_style | FontStyle::ITALIC; }
+                font.set_style(current_style);
+                let surface = font.render(glyph_str).blended(color).map_err(|e| e.to_string())?;
+                font.set_style(original_style); // Reset style
+
+                let texture = self.texture_creator
+                    .create_texture_from_surface(&surface)
+                    .map_err(|e| e.to_string())?;
+                self.glyph_cache.put(key.clone(), texture);
+            }
 
-        let texture_creator = canvas.texture_creator();
-        let surface = self.font
-            .render(text)
-            .blended(Color::RGBA(255, 255, 255, 255))
-            .map_err(|e| e.to_string())?;
-        let texture = texture_creator
-            .create_texture_from_surface(&surface)
-            .map_err(|e| e.to_string())?;
-        let target_rect = Rect::new(x, y, surface.width(), surface.height());
-        canvas.copy(&texture, None, Some(target_rect))?;
+            let texture = self.glyph_cache.get(&key).expect("glyph was just cached");
+            let query = texture.query();
+            let target_rect = Rect::new(cursor_x, y, query.width, query.height);
+            canvas.copy(texture, None, Some(target_rect))?;
+            cursor_x += query.width as i32;
+            max_height = max_height.max(query.height);
+            if i + 1 < total_chars {
+                cursor_x += self.letter_spacing;
+            }
+        }
 
-        self.font.set_style(original_style); // Reset style
-        Ok((surface.width(), surface.height()))
+        Ok(((cursor_x - x) as u32, max_height))
     }
     
     pub fn draw_single_line(&mut self, canvas: &mut Canvas<Window>, text: &str, x: i32, y: i32, ) -> Result<(), String> {
@@ -287,7 +800,7 @@ impl<'a, 'b> FontManager<'a, 'b> {
             let mid = (low + high) / 2;
             // load font at trial size
             let trial = self.ttf_context
-                .load_font(&config.font_path, mid)
+                .load_font(&config.profile.normal.path, mid)
                 .map_err(|e| e.to_string())?;
             // wrap & measure
             let surf = trial
@@ -323,16 +836,17 @@ impl<'a, 'b> FontManager<'a, 'b> {
         box_height: u32,
         min_pt: u16,
         max_pt: u16,
+        text_color: Color,
     ) -> Result<(Surface<'static>, u32, u32), String> {
         let config = Config::new();
         let best_pt = self.find_fitting_size(text, box_width, box_height, min_pt, max_pt)?;
         let font = self
             .ttf_context
-            .load_font(&config.font_path, best_pt)
+            .load_font(&config.profile.normal.path, best_pt)
             .map_err(|e| e.to_string())?;
         let surface = font
             .render(text)
-            .blended_wrapped(Color::RGBA(255, 255, 255, 255), box_width)
+            .blended_wrapped(text_color, box_width)
             .map_err(|e| e.to_string())?;
         let (width, height) = (surface.width(), surface.height());
         Ok((surface, width, height))
@@ -362,7 +876,7 @@ impl<'a, 'b> FontManager<'a, 'b> {
 
         // 2) reload font at that size
         let new_font = self.ttf_context
-            .load_font(config.font_path, best_pt)
+            .load_font(config.profile.normal.path.as_path(), best_pt)
             .map_err(|e| e.to_string())?;
 
         // 3) wrap & render into a surface
@@ -403,20 +917,33 @@ mod tests {
 
     // FIX: Use a static OnceLock to ensure the TTF context is initialized exactly once for all tests.
     static TTF_CONTEXT: OnceLock<Sdl2TtfContext> = OnceLock::new();
+    // The glyph cache needs a TextureCreator; a hidden 1x1 window gives us one
+    // without putting anything on screen during a test run.
+    static TEXTURE_CREATOR: OnceLock<TextureCreator<WindowContext>> = OnceLock::new();
 
     // Test helper to create a FontManager.
-    fn setup_font_manager() -> FontManager<'static, 'static> {
+    fn setup_font_manager() -> FontManager<'static, 'static, 'static> {
         // This will initialize the context on the first call and simply return
         // the existing context on all subsequent calls from other tests.
         let ttf_context = TTF_CONTEXT.get_or_init(|| {
             sdl2::ttf::init().expect("Failed to initialize SDL2 TTF context for tests")
         });
+        let texture_creator = TEXTURE_CREATOR.get_or_init(|| {
+            let sdl_context = sdl2::init().expect("Failed to initialize SDL2 for tests");
+            let video_subsystem = sdl_context.video().expect("Failed to initialize SDL2 video subsystem for tests");
+            let window = video_subsystem
+                .window("font test", 1, 1)
+                .hidden()
+                .build()
+                .expect("Failed to create hidden test window");
+            window.into_canvas().build().expect("Failed to create canvas for tests").texture_creator()
+        });
 
         // NOTE: This test requires a font file at the specified path.
         // A common font like DejaVuSans is used here, which is often found on Linux.
         // For other systems, you may need to change this path or place a font at `tests/font.ttf`.
         let font_path = "/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf";
-        FontManager::new(ttf_context, font_path, 16).expect("Failed to load font for testing")
+        FontManager::new(ttf_context, font_path, 16, texture_creator).expect("Failed to load font for testing")
     }
 
     #[test]
@@ -0,0 +1,43 @@
+// src/ui/media.rs
+// Caches SDL2 textures decoded from extracted .apkg media files, the same
+// load-once-then-reuse idea as `FontManager`'s glyph cache.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use sdl2::image::LoadTexture;
+use sdl2::render::{Texture, TextureCreator};
+use sdl2::video::WindowContext;
+
+/// Loads and caches image textures referenced by card media, keyed by their
+/// path on disk so a picture reused across cards (or reviewed again later
+/// the same session) is decoded only once.
+pub struct MediaCache<'c> {
+    texture_creator: &'c TextureCreator<WindowContext>,
+    // `None` caches a load that failed, so a missing/corrupt asset is only
+    // ever attempted once instead of every frame it's on screen.
+    textures: HashMap<PathBuf, Option<Texture<'c>>>,
+}
+
+impl<'c> MediaCache<'c> {
+    pub fn new(texture_creator: &'c TextureCreator<WindowContext>) -> Self {
+        Self { texture_creator, textures: HashMap::new() }
+    }
+
+    /// Returns the cached texture for `path`, decoding and inserting it on
+    /// first use. A file that fails to decode (missing, corrupt, unsupported
+    /// format) returns `None` and is not retried every frame.
+    pub fn get_or_load(&mut self, path: &Path) -> Option<&Texture<'c>> {
+        let texture_creator = self.texture_creator;
+        self.textures
+            .entry(path.to_path_buf())
+            .or_insert_with(|| match texture_creator.load_texture(path) {
+                Ok(texture) => Some(texture),
+                Err(e) => {
+                    println!("Failed to load media texture {:?}: {}", path, e);
+                    None
+                }
+            })
+            .as_ref()
+    }
+}
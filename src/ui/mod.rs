@@ -2,9 +2,15 @@
 // This module contains all components related to the User Interface.
 
 pub mod canvas; // For upscaling our view
-pub mod font;   // For text 
+pub mod device_profile; // Per-screen font tiers and layout margins
+pub mod font;   // For text
+pub mod media;  // Cached textures for card images
 pub mod sprite; // For cute sprites (not yet implemented)
+pub mod theme;  // User-customizable color palettes
 
 pub use self::canvas::CanvasManager;
+pub use self::device_profile::DeviceProfile;
 pub use self::font::FontManager;
+pub use self::media::MediaCache;
 pub use self::sprite::Sprite;
+pub use self::theme::{Theme, lerp_color};
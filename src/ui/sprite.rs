@@ -1,57 +1,171 @@
 // src/ui/sprite.rs
 // Manages the animated "mother" sprite.
 
-use sdl2::pixels::Color;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use sdl2::image::LoadTexture;
 use sdl2::rect::Rect;
-use sdl2::render::Canvas;
-use sdl2::video::Window;
-use std::time::Instant;
+use sdl2::render::{Canvas, Texture, TextureCreator};
+use sdl2::video::{Window, WindowContext};
+
+use crate::scheduler::Rating;
+use crate::ui::Theme;
 
-// Represents the different emotional states of the sprite.
+/// Represents the different emotional states of the sprite.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SpriteState {
     Idle,
-    // We'll add more later, like Correct, Incorrect, etc.
+    Correct,
+    Incorrect,
+    SessionComplete,
+}
+
+/// One frame of a state's animation: its rectangle within the sprite sheet
+/// and how long to hold it before advancing to the next frame.
+struct Frame {
+    rect: Rect,
+    duration_ms: u64,
 }
 
-pub struct Sprite {
+/// Each state occupies one row of the sprite sheet, with square frames this
+/// size laid out left to right.
+const FRAME_SIZE: u32 = 32;
+/// How long a reaction plays before falling back to `Idle`. `SessionComplete`
+/// isn't timed out this way — it's left up to the caller to start a new
+/// session (and thus a new `Idle`/reaction cycle).
+const REACTION_DURATION: Duration = Duration::from_millis(900);
+
+/// Mostly-open idle loop with a brief blink: eyes open for 1.8s, then closed
+/// for 150ms.
+fn idle_frames() -> &'static [Frame] {
+    &[
+        Frame { rect: Rect::new(0, 0, FRAME_SIZE, FRAME_SIZE), duration_ms: 1800 },
+        Frame { rect: Rect::new(32, 0, FRAME_SIZE, FRAME_SIZE), duration_ms: 150 },
+    ]
+}
+
+fn correct_frames() -> &'static [Frame] {
+    &[
+        Frame { rect: Rect::new(0, 32, FRAME_SIZE, FRAME_SIZE), duration_ms: 200 },
+        Frame { rect: Rect::new(32, 32, FRAME_SIZE, FRAME_SIZE), duration_ms: 200 },
+        Frame { rect: Rect::new(64, 32, FRAME_SIZE, FRAME_SIZE), duration_ms: 300 },
+    ]
+}
+
+fn incorrect_frames() -> &'static [Frame] {
+    &[
+        Frame { rect: Rect::new(0, 64, FRAME_SIZE, FRAME_SIZE), duration_ms: 250 },
+        Frame { rect: Rect::new(32, 64, FRAME_SIZE, FRAME_SIZE), duration_ms: 400 },
+    ]
+}
+
+fn session_complete_frames() -> &'static [Frame] {
+    &[
+        Frame { rect: Rect::new(0, 96, FRAME_SIZE, FRAME_SIZE), duration_ms: 300 },
+        Frame { rect: Rect::new(32, 96, FRAME_SIZE, FRAME_SIZE), duration_ms: 300 },
+        Frame { rect: Rect::new(64, 96, FRAME_SIZE, FRAME_SIZE), duration_ms: 300 },
+    ]
+}
+
+fn frames_for(state: SpriteState) -> &'static [Frame] {
+    match state {
+        SpriteState::Idle => idle_frames(),
+        SpriteState::Correct => correct_frames(),
+        SpriteState::Incorrect => incorrect_frames(),
+        SpriteState::SessionComplete => session_complete_frames(),
+    }
+}
+
+pub struct Sprite<'c> {
+    /// `None` when `sprite_sheet_path` failed to load (missing/corrupt file);
+    /// `draw` then falls back to the old placeholder shape so a bad asset
+    /// degrades gracefully instead of drawing nothing.
+    sheet: Option<Texture<'c>>,
     state: SpriteState,
+    frame_index: usize,
     last_frame_time: Instant,
-    is_blinking: bool,
+    /// When the current reaction should end and fall back to `Idle`.
+    reaction_until: Option<Instant>,
 }
 
-impl Sprite {
-    pub fn new() -> Self {
+impl<'c> Sprite<'c> {
+    pub fn new(sprite_sheet_path: &Path, texture_creator: &'c TextureCreator<WindowContext>) -> Self {
+        let sheet = texture_creator.load_texture(sprite_sheet_path).ok();
+        if sheet.is_none() {
+            println!("Failed to load sprite sheet {:?}; using placeholder shapes.", sprite_sheet_path);
+        }
+
         Sprite {
+            sheet,
             state: SpriteState::Idle,
+            frame_index: 0,
             last_frame_time: Instant::now(),
-            is_blinking: false,
+            reaction_until: None,
         }
     }
 
+    /// Plays the `Correct`/`Incorrect` reaction for a just-graded card,
+    /// falling back to `Idle` once `REACTION_DURATION` elapses. Called by the
+    /// studying input handler right after a rating is applied.
+    pub fn react(&mut self, rating: Rating) {
+        let reaction = match rating {
+            Rating::Again | Rating::Hard => SpriteState::Incorrect,
+            Rating::Good | Rating::Easy => SpriteState::Correct,
+        };
+        self.enter_state(reaction, Some(Instant::now() + REACTION_DURATION));
+    }
+
+    /// Plays the `SessionComplete` animation. Sticky until the caller starts
+    /// a new studying session (there's no card left to react to in the
+    /// meantime).
+    pub fn session_complete(&mut self) {
+        self.enter_state(SpriteState::SessionComplete, None);
+    }
+
+    fn enter_state(&mut self, state: SpriteState, reaction_until: Option<Instant>) {
+        self.state = state;
+        self.frame_index = 0;
+        self.last_frame_time = Instant::now();
+        self.reaction_until = reaction_until;
+    }
+
     /// Updates the sprite's animation state. Should be called once per frame.
     pub fn update(&mut self) {
-        // For idle, we'll make the sprite blink every so often.
-        if self.last_frame_time.elapsed().as_millis() > 500 {
-            self.is_blinking = !self.is_blinking;
+        if let Some(until) = self.reaction_until {
+            if Instant::now() >= until {
+                self.enter_state(SpriteState::Idle, None);
+            }
+        }
+
+        let frames = frames_for(self.state);
+        let current = &frames[self.frame_index % frames.len()];
+        if self.last_frame_time.elapsed() >= Duration::from_millis(current.duration_ms) {
+            self.frame_index = (self.frame_index + 1) % frames.len();
             self.last_frame_time = Instant::now();
         }
     }
 
     /// Draws the sprite to the canvas.
-    pub fn draw(&self, canvas: &mut Canvas<Window>) -> Result<(), String> {
-        // Placeholder sprite drawing logic.
-        // **FIXED**: Positioned the sprite in the bottom right corner, aligned with the control hints.
+    pub fn draw(&self, canvas: &mut Canvas<Window>, theme: &Theme) -> Result<(), String> {
         let base_rect = Rect::new(470, 330, 32, 32);
-        
-        // Draw body
-        canvas.set_draw_color(Color::RGB(200, 200, 255));
+        let frames = frames_for(self.state);
+        let frame = &frames[self.frame_index % frames.len()];
+
+        if let Some(sheet) = &self.sheet {
+            canvas.copy(sheet, Some(frame.rect), Some(base_rect))?;
+            return Ok(());
+        }
+
+        // Placeholder sprite drawing logic, used when the sheet failed to load.
+        canvas.set_draw_color(theme.sprite_body);
         canvas.fill_rect(base_rect)?;
 
-        // Draw eyes
-        canvas.set_draw_color(Color::RGB(0, 0, 0));
-        if !self.is_blinking {
-            let eye1 = Rect::new(476, 340, 5, 5); // Adjusted y-coordinate
-            let eye2 = Rect::new(488, 340, 5, 5); // Adjusted y-coordinate
+        canvas.set_draw_color(theme.sprite_eye);
+        let blinking = self.state == SpriteState::Idle && self.frame_index == 1;
+        if !blinking {
+            let eye1 = Rect::new(476, 340, 5, 5);
+            let eye2 = Rect::new(488, 340, 5, 5);
             canvas.fill_rects(&[eye1, eye2])?;
         }
 
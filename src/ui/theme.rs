@@ -0,0 +1,162 @@
+// src/ui/theme.rs
+// User-customizable color palettes loaded from TOML, so a handheld owner can
+// reskin CardBrick without recompiling. Falls back to `Theme::default_theme()`
+// for any color a theme file doesn't specify.
+
+use std::path::Path;
+use sdl2::pixels::Color;
+
+/// A resolved set of colors for every themeable surface in the app. Built
+/// either from `default_theme()` or by overlaying a `themes_directory/<name>.toml`
+/// file (and whatever it `derive`s from) on top of it.
+#[derive(Clone)]
+pub struct Theme {
+    pub name: String,
+    pub background: Color,
+    pub text: Color,
+    pub progress_empty: Color,
+    pub progress_full_low: Color,
+    pub progress_full_high: Color,
+    pub menu_highlight: Color,
+    pub sprite_body: Color,
+    pub sprite_eye: Color,
+    pub hint_text: Color,
+}
+
+/// Perceived-luminance threshold above which a background is bright enough
+/// that light text would wash out against it.
+const LIGHT_BACKGROUND_LUMINANCE: f32 = 140.0;
+/// How deep a `derive = "..."` chain may go before `load` gives up, so a
+/// theme file that names itself (or a cycle) as its own parent can't hang.
+const MAX_DERIVE_DEPTH: u32 = 8;
+
+/// Linearly interpolates between two colors, e.g. blending a theme's
+/// `progress_full_low`/`progress_full_high` by how complete a session is.
+/// `t` is clamped to `[0.0, 1.0]`.
+pub fn lerp_color(a: Color, b: Color, t: f32) -> Color {
+    let t = t.clamp(0.0, 1.0);
+    let lerp_channel = |from: u8, to: u8| (from as f32 + (to as f32 - from as f32) * t) as u8;
+    Color::RGB(
+        lerp_channel(a.r, b.r),
+        lerp_channel(a.g, b.g),
+        lerp_channel(a.b, b.b),
+    )
+}
+
+fn perceived_luminance(c: Color) -> f32 {
+    0.299 * c.r as f32 + 0.587 * c.g as f32 + 0.114 * c.b as f32
+}
+
+impl Theme {
+    /// The theme CardBrick has always shipped with: the exact colors that
+    /// used to be hardcoded directly in `draw_studying_scene`,
+    /// `draw_main_menu_scene`, and `Sprite::draw`.
+    pub fn default_theme() -> Self {
+        Self {
+            name: "default".to_string(),
+            background: Color::RGB(40, 40, 45),
+            text: Color::RGB(255, 255, 255),
+            progress_empty: Color::RGB(60, 60, 60),
+            progress_full_low: Color::RGB(255, 0, 80),
+            progress_full_high: Color::RGB(0, 255, 80),
+            menu_highlight: Color::RGB(80, 80, 80),
+            sprite_body: Color::RGB(200, 200, 255),
+            sprite_eye: Color::RGB(0, 0, 0),
+            hint_text: Color::RGB(255, 255, 255),
+        }
+    }
+
+    /// Loads `themes_directory/<name>.toml`, overlaying its keys on top of
+    /// whichever parent it `derive`s from (recursively), or `default_theme()`
+    /// if it declares no parent. Falls back to `default_theme()` entirely if
+    /// the file is missing or invalid. Automatically darkens `text`/
+    /// `hint_text` if the resolved `background` turns out to be light enough
+    /// that the default light text would be unreadable.
+    pub fn load(themes_directory: &Path, name: &str) -> Self {
+        match Self::load_named(themes_directory, name, 0) {
+            Ok(theme) => theme.with_readable_text(),
+            Err(e) => {
+                eprintln!("ignoring invalid theme '{}': {}", name, e);
+                Self::default_theme()
+            }
+        }
+    }
+
+    fn load_named(themes_directory: &Path, name: &str, depth: u32) -> Result<Self, String> {
+        if depth > MAX_DERIVE_DEPTH {
+            return Err(format!("derive chain through '{}' is too deep", name));
+        }
+
+        let path = themes_directory.join(format!("{}.toml", name));
+        let contents = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
+        let value: toml::Value = contents.parse().map_err(|e: toml::de::Error| e.to_string())?;
+        let table = value.as_table().ok_or("theme file must be a table")?;
+
+        let mut theme = match table.get("derive").and_then(|v| v.as_str()) {
+            Some(parent) => Self::load_named(themes_directory, parent, depth + 1)?,
+            None => Self::default_theme(),
+        };
+
+        if let Some(declared_name) = table.get("name").and_then(|v| v.as_str()) {
+            if declared_name != name {
+                log::warn!(
+                    "theme {:?} declares name '{}' but is loaded as '{}'",
+                    path, declared_name, name
+                );
+            }
+        }
+        theme.name = name.to_string();
+
+        overlay_color(table, "background", &mut theme.background);
+        overlay_color(table, "text", &mut theme.text);
+        overlay_color(table, "progress_empty", &mut theme.progress_empty);
+        overlay_color(table, "progress_full_low", &mut theme.progress_full_low);
+        overlay_color(table, "progress_full_high", &mut theme.progress_full_high);
+        overlay_color(table, "menu_highlight", &mut theme.menu_highlight);
+        overlay_color(table, "sprite_body", &mut theme.sprite_body);
+        overlay_color(table, "sprite_eye", &mut theme.sprite_eye);
+        overlay_color(table, "hint_text", &mut theme.hint_text);
+
+        Ok(theme)
+    }
+
+    fn with_readable_text(mut self) -> Self {
+        if perceived_luminance(self.background) > LIGHT_BACKGROUND_LUMINANCE {
+            self.text = Color::RGB(20, 20, 20);
+            self.hint_text = Color::RGB(20, 20, 20);
+        }
+        self
+    }
+}
+
+fn overlay_color(table: &toml::value::Table, key: &str, slot: &mut Color) {
+    if let Some(color) = table.get(key).and_then(parse_color) {
+        *slot = color;
+    }
+}
+
+/// Accepts either a hex string (`"#3C3C3C"`, with or without the `#`) or an
+/// `[r, g, b]` triple.
+fn parse_color(value: &toml::Value) -> Option<Color> {
+    match value {
+        toml::Value::String(s) => parse_hex_color(s),
+        toml::Value::Array(components) if components.len() == 3 => {
+            let r = components[0].as_integer()? as u8;
+            let g = components[1].as_integer()? as u8;
+            let b = components[2].as_integer()? as u8;
+            Some(Color::RGB(r, g, b))
+        }
+        _ => None,
+    }
+}
+
+fn parse_hex_color(s: &str) -> Option<Color> {
+    let hex = s.strip_prefix('#').unwrap_or(s);
+    if hex.len() != 6 {
+        return None;
+    }
+    let r = u8::from_str_radix(&hex[0..2], 16).ok()?;
+    let g = u8::from_str_radix(&hex[2..4], 16).ok()?;
+    let b = u8::from_str_radix(&hex[4..6], 16).ok()?;
+    Some(Color::RGB(r, g, b))
+}